@@ -1,7 +1,7 @@
 use crate::str_intern::InternedStr;
 use crate::tokens::{Keyword, Literal, Symbol, Token};
 use crate::util::CompilerResult;
-use std::fmt::Display;
+use std::fmt::{self, Display};
 use std::sync::Arc;
 
 /*
@@ -224,7 +224,7 @@ impl TryFrom<&Token> for UnaryOp {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum BinaryOp {
     Add,
     Subtract,
@@ -269,6 +269,22 @@ impl BinaryOp {
             Assign(_) => 1,
         }
     }
+
+    // A flat precedence number can't tell a precedence-climbing parser which
+    // side to recurse on for operators at the same level: `a - b - c` must
+    // parse as `(a - b) - c` while `a = b = c` must parse as `a = (b = c)`.
+    pub fn fixity(&self) -> Fixity {
+        match self {
+            BinaryOp::Assign(_) => Fixity::Right,
+            _ => Fixity::Left,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fixity {
+    Left,
+    Right,
 }
 
 impl TryFrom<&Token> for BinaryOp {
@@ -313,7 +329,7 @@ impl TryFrom<&Token> for BinaryOp {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, PartialEq, Eq)]
 pub enum AssignOp {
     Assign,
     Plus,
@@ -348,3 +364,440 @@ impl TryFrom<&Token> for AssignOp {
         }
     }
 }
+
+fn fmt_indent(f: &mut fmt::Formatter, level: usize) -> fmt::Result {
+    for _ in 0..level {
+        write!(f, "    ")?;
+    }
+    Ok(())
+}
+
+impl Display for InitDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InitDeclaration::Declaration(decl) => write!(f, "{decl};"),
+            InitDeclaration::Function(func) => write!(f, "{func}"),
+            InitDeclaration::Struct(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+impl Display for StructDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "struct {} {{", self.ident)?;
+        for member in &self.members {
+            writeln!(f, "    {member};")?;
+        }
+        write!(f, "}};")
+    }
+}
+
+impl Display for FunctionDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}(", self.declaration)?;
+        for (i, param) in self.parameters.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{param}")?;
+        }
+        if self.varargs {
+            if !self.parameters.is_empty() {
+                write!(f, ", ")?;
+            }
+            write!(f, "...")?;
+        }
+        write!(f, ")")?;
+        match &self.body {
+            Some(block) => write!(f, " {block}"),
+            None => write!(f, ";"),
+        }
+    }
+}
+
+impl Display for VariableDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.declaration)?;
+        if let Some(init) = &self.initializer {
+            write!(f, " = {init}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Declaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.ident.map(|i| i.to_string()).unwrap_or_default();
+        write!(f, "{} {}", self.specifier, fmt_declarator(&self.declarator, &name))
+    }
+}
+
+// Builds the declarator around `name` inside-out, so `Pointer { to: Array }`
+// (pointer to array) renders as `(*name)[N]` and `Array { of: Pointer }`
+// (array of pointers) renders as `*name[N]`, matching C declarator grammar.
+fn fmt_declarator(declarator: &DeclaratorType, name: &str) -> String {
+    match declarator {
+        DeclaratorType::None => name.to_string(),
+        DeclaratorType::Pointer { to } => fmt_declarator(to, &format!("*{name}")),
+        DeclaratorType::Array { of, size } => {
+            let suffix = match size {
+                Some(n) => format!("[{n}]"),
+                None => "[]".to_string(),
+            };
+            let name = if name.starts_with('*') { format!("({name})") } else { name.to_string() };
+            fmt_declarator(of, &format!("{name}{suffix}"))
+        }
+    }
+}
+
+impl Display for DeclaratorType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", fmt_declarator(self, ""))
+    }
+}
+
+impl Display for DeclarationSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut parts = Vec::new();
+        for specifier in &self.specifiers {
+            parts.push(specifier.to_string());
+        }
+        for qualifier in &self.qualifiers {
+            parts.push(qualifier.to_string());
+        }
+        for ty in &self.ty {
+            parts.push(ty.to_string());
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+impl Display for TypeSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use TypeSpecifier::*;
+        match self {
+            Void => write!(f, "void"),
+            Char => write!(f, "char"),
+            Int => write!(f, "int"),
+            Long => write!(f, "long"),
+            Double => write!(f, "double"),
+            Signed => write!(f, "signed"),
+            Unsigned => write!(f, "unsigned"),
+            Struct(ident) => write!(f, "struct {ident}"),
+        }
+    }
+}
+
+impl Display for StorageSpecifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StorageSpecifier::Static => write!(f, "static"),
+        }
+    }
+}
+
+impl Display for TypeQualifier {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeQualifier::Const => write!(f, "const"),
+        }
+    }
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{{")?;
+        for stmt in &self.0 {
+            fmt_statement(f, stmt, 1)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt_statement(f, self, 0)
+    }
+}
+
+fn fmt_statement(f: &mut fmt::Formatter, stmt: &Statement, level: usize) -> fmt::Result {
+    fmt_indent(f, level)?;
+    match stmt {
+        Statement::Expression(expr) => writeln!(f, "{expr};"),
+        Statement::Declaration(decl) => writeln!(f, "{decl};"),
+        Statement::Break => writeln!(f, "break;"),
+        Statement::Continue => writeln!(f, "continue;"),
+        Statement::Return(Some(expr)) => writeln!(f, "return {expr};"),
+        Statement::Return(None) => writeln!(f, "return;"),
+        Statement::Block(block) => {
+            writeln!(f, "{{")?;
+            for s in &block.0 {
+                fmt_statement(f, s, level + 1)?;
+            }
+            fmt_indent(f, level)?;
+            writeln!(f, "}}")
+        }
+        Statement::If(cond, then, otherwise) => {
+            writeln!(f, "if ({cond})")?;
+            fmt_statement(f, then, level + 1)?;
+            if let Some(otherwise) = otherwise {
+                fmt_indent(f, level)?;
+                writeln!(f, "else")?;
+                fmt_statement(f, otherwise, level + 1)?;
+            }
+            Ok(())
+        }
+        Statement::While(cond, body) => {
+            writeln!(f, "while ({cond})")?;
+            fmt_statement(f, body, level + 1)
+        }
+        Statement::For(init, cond, step, body) => {
+            let init = init.as_ref().map(|d| d.to_string()).unwrap_or_default();
+            let cond = cond.as_ref().map(|e| e.to_string()).unwrap_or_default();
+            let step = step.as_ref().map(|e| e.to_string()).unwrap_or_default();
+            writeln!(f, "for ({init}; {cond}; {step})")?;
+            fmt_statement(f, body, level + 1)
+        }
+    }
+}
+
+impl Display for TypeOrExpression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TypeOrExpression::Type(decl) => write!(f, "{decl}"),
+            TypeOrExpression::Expr(expr) => write!(f, "{expr}"),
+        }
+    }
+}
+
+// An operand needs parens only when, standing alone, it would parse back as
+// something looser-binding than the slot it sits in. For the operand of a
+// unary operator or cast, that's only `Binary`, since unary/postfix/cast/
+// index/member access all bind tighter than any binary operator.
+fn fmt_primary_operand(f: &mut fmt::Formatter, expr: &Expression) -> fmt::Result {
+    if matches!(expr, Expression::Binary(..)) {
+        write!(f, "({expr})")
+    } else {
+        write!(f, "{expr}")
+    }
+}
+
+// The base of `PostFix`/`Index`/`Member`/`PointerMember` sits in a postfix
+// slot, which binds tighter than any prefix form, so a `Unary`, `Cast` or
+// `Sizeof` base needs parens there as well: `(*p).x` must not print as `*p.x`.
+fn fmt_postfix_base(f: &mut fmt::Formatter, expr: &Expression) -> fmt::Result {
+    if matches!(
+        expr,
+        Expression::Binary(..) | Expression::Unary(..) | Expression::Cast(..) | Expression::Sizeof(..)
+    ) {
+        write!(f, "({expr})")
+    } else {
+        write!(f, "{expr}")
+    }
+}
+
+// Two adjacent unary operators whose symbols share a character at the seam
+// (`- -x`, `+ +x`, `& &x`) would re-lex as a single `--`/`++`/`&&` token, so a
+// space is forced between them; anything else concatenates unambiguously.
+fn needs_space_before_nested_unary(op: &UnaryOp, inner: &Expression) -> bool {
+    if let Expression::Unary(inner_op, _) = inner {
+        let outer = op.to_string();
+        let inner = inner_op.to_string();
+        if let (Some(a), Some(b)) = (outer.chars().last(), inner.chars().next()) {
+            return a == b;
+        }
+    }
+    false
+}
+
+enum Side {
+    Left,
+    Right,
+}
+
+fn fmt_binary_operand(
+    f: &mut fmt::Formatter,
+    child: &Expression,
+    parent_prec: u8,
+    side: Side,
+    parent_right_assoc: bool,
+) -> fmt::Result {
+    if let Expression::Binary(child_op, ..) = child {
+        let child_prec = child_op.precedence();
+        let wrong_side = match side {
+            Side::Left => parent_right_assoc,
+            Side::Right => !parent_right_assoc,
+        };
+        if child_prec < parent_prec || (child_prec == parent_prec && wrong_side) {
+            return write!(f, "({child})");
+        }
+    }
+    write!(f, "{child}")
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expression::Literal(lit) => write!(f, "{lit}"),
+            Expression::Variable(ident) => write!(f, "{ident}"),
+            Expression::Sizeof(inner) => write!(f, "sizeof({inner})"),
+            Expression::Parenthesized(inner) => write!(f, "({inner})"),
+            Expression::PostFix(op, inner) => {
+                fmt_postfix_base(f, inner)?;
+                write!(f, "{op}")
+            }
+            Expression::Unary(op, inner) => {
+                write!(f, "{op}")?;
+                if needs_space_before_nested_unary(op, inner) {
+                    write!(f, " ")?;
+                }
+                fmt_primary_operand(f, inner)
+            }
+            Expression::Binary(op, lhs, rhs) => {
+                let prec = op.precedence();
+                let right_assoc = op.fixity() == Fixity::Right;
+                fmt_binary_operand(f, lhs, prec, Side::Left, right_assoc)?;
+                write!(f, " {op} ")?;
+                fmt_binary_operand(f, rhs, prec, Side::Right, right_assoc)
+            }
+            Expression::FunctionCall(ident, args) => {
+                write!(f, "{ident}(")?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{arg}")?;
+                }
+                write!(f, ")")
+            }
+            Expression::Index(base, index) => {
+                fmt_postfix_base(f, base)?;
+                write!(f, "[{index}]")
+            }
+            Expression::Member(base, field) => {
+                fmt_postfix_base(f, base)?;
+                write!(f, ".{field}")
+            }
+            Expression::PointerMember(base, field) => {
+                fmt_postfix_base(f, base)?;
+                write!(f, "->{field}")
+            }
+            Expression::Cast(spec, inner) => {
+                write!(f, "({spec})")?;
+                fmt_primary_operand(f, inner)
+            }
+        }
+    }
+}
+
+impl Display for PostfixOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PostfixOp::Increment => write!(f, "++"),
+            PostfixOp::Decrement => write!(f, "--"),
+        }
+    }
+}
+
+impl Display for UnaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnaryOp::Increment => write!(f, "++"),
+            UnaryOp::Decrement => write!(f, "--"),
+            UnaryOp::Plus => write!(f, "+"),
+            UnaryOp::Negate => write!(f, "-"),
+            UnaryOp::LogicalNot => write!(f, "!"),
+            UnaryOp::BitwiseNot => write!(f, "~"),
+            UnaryOp::Deref => write!(f, "*"),
+            UnaryOp::AddressOf => write!(f, "&"),
+        }
+    }
+}
+
+impl Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use BinaryOp::*;
+        match self {
+            Add => write!(f, "+"),
+            Subtract => write!(f, "-"),
+            Multiply => write!(f, "*"),
+            Divide => write!(f, "/"),
+            Modulo => write!(f, "%"),
+            Equal => write!(f, "=="),
+            NotEqual => write!(f, "!="),
+            GreaterThan => write!(f, ">"),
+            GreaterThanEqual => write!(f, ">="),
+            LessThan => write!(f, "<"),
+            LessThanEqual => write!(f, "<="),
+            LogicalAnd => write!(f, "&&"),
+            LogicalOr => write!(f, "||"),
+            BitwiseAnd => write!(f, "&"),
+            BitwiseOr => write!(f, "|"),
+            BitwiseXor => write!(f, "^"),
+            LeftShift => write!(f, "<<"),
+            RightShift => write!(f, ">>"),
+            Assign(op) => write!(f, "{op}"),
+        }
+    }
+}
+
+impl Display for AssignOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssignOp::Assign => write!(f, "="),
+            AssignOp::Plus => write!(f, "+="),
+            AssignOp::Minus => write!(f, "-="),
+            AssignOp::Multiply => write!(f, "*="),
+            AssignOp::Divide => write!(f, "/="),
+            AssignOp::Modulo => write!(f, "%="),
+            AssignOp::BitwiseAnd => write!(f, "&="),
+            AssignOp::BitwiseOr => write!(f, "|="),
+            AssignOp::BitwiseXor => write!(f, "^="),
+            AssignOp::LeftShift => write!(f, "<<="),
+            AssignOp::RightShift => write!(f, ">>="),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit(n: i64) -> Expression {
+        Expression::Literal(Literal::Integer(n))
+    }
+
+    fn binary(op: BinaryOp, lhs: Expression, rhs: Expression) -> Expression {
+        Expression::Binary(op, Box::new(lhs), Box::new(rhs))
+    }
+
+    #[test]
+    fn left_associative_chain_round_trips_without_parens() {
+        let expr = binary(BinaryOp::Subtract, binary(BinaryOp::Subtract, lit(1), lit(2)), lit(3));
+        assert_eq!(expr.to_string(), "1 - 2 - 3");
+    }
+
+    #[test]
+    fn right_operand_of_same_precedence_keeps_parens() {
+        let expr = binary(BinaryOp::Subtract, lit(1), binary(BinaryOp::Subtract, lit(2), lit(3)));
+        assert_eq!(expr.to_string(), "1 - (2 - 3)");
+    }
+
+    #[test]
+    fn index_base_parenthesizes_lower_precedence_binary() {
+        let expr = Expression::Index(Box::new(binary(BinaryOp::Add, lit(1), lit(2))), Box::new(lit(0)));
+        assert_eq!(expr.to_string(), "(1 + 2)[0]");
+    }
+
+    #[test]
+    fn nested_same_symbol_unary_gets_a_separating_space() {
+        let expr = Expression::Unary(UnaryOp::Negate, Box::new(Expression::Unary(UnaryOp::Negate, Box::new(lit(5)))));
+        assert_eq!(expr.to_string(), "- -5");
+    }
+
+    #[test]
+    fn nested_different_symbol_unary_needs_no_space() {
+        let expr = Expression::Unary(UnaryOp::Negate, Box::new(Expression::Unary(UnaryOp::BitwiseNot, Box::new(lit(1)))));
+        assert_eq!(expr.to_string(), "-~1");
+    }
+}
@@ -0,0 +1,563 @@
+use crate::hir::{Coercion, HirDeclaration, HirExpr, HirExprKind, HirFunction, HirStatement, Type};
+use crate::str_intern::InternedStr;
+use crate::util::CompilerResult;
+use inkwell::basic_block::BasicBlock;
+use inkwell::builder::Builder;
+use inkwell::context::Context;
+use inkwell::module::Module;
+use inkwell::types::{BasicMetadataTypeEnum, BasicType, BasicTypeEnum};
+use inkwell::values::{BasicMetadataValueEnum, BasicValueEnum, FunctionValue, PointerValue};
+use inkwell::{FloatPredicate, IntPredicate};
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub enum CodegenError {
+    UnknownFunction(InternedStr),
+    UnknownVariable(InternedStr),
+    Unsupported(&'static str),
+}
+
+// One loop's break/continue targets; pushed on entry to `While`/`For` and
+// popped on exit so a nested loop's `break` doesn't escape to the outer one.
+struct LoopTargets<'ctx> {
+    continue_block: BasicBlock<'ctx>,
+    break_block: BasicBlock<'ctx>,
+}
+
+pub struct Codegen<'ctx> {
+    context: &'ctx Context,
+    module: Module<'ctx>,
+    builder: Builder<'ctx>,
+    locals: Vec<HashMap<InternedStr, PointerValue<'ctx>>>,
+    functions: HashMap<InternedStr, FunctionValue<'ctx>>,
+    globals: HashMap<InternedStr, PointerValue<'ctx>>,
+    loop_stack: Vec<LoopTargets<'ctx>>,
+}
+
+impl<'ctx> Codegen<'ctx> {
+    pub fn new(context: &'ctx Context, module_name: &str) -> Self {
+        Codegen {
+            context,
+            module: context.create_module(module_name),
+            builder: context.create_builder(),
+            locals: Vec::new(),
+            functions: HashMap::new(),
+            globals: HashMap::new(),
+            loop_stack: Vec::new(),
+        }
+    }
+
+    pub fn compile(mut self, decls: &[HirDeclaration]) -> CompilerResult<Module<'ctx>, CodegenError> {
+        // Functions can call each other regardless of declaration order, so
+        // every signature is declared up front before any body is lowered.
+        for decl in decls {
+            if let HirDeclaration::Function(func) = decl {
+                self.declare_function(func)?;
+            }
+        }
+        for decl in decls {
+            match decl {
+                HirDeclaration::Function(func) => self.lower_function(func)?,
+                HirDeclaration::Global(ident, ty, init) => self.lower_global(*ident, ty, init.as_ref())?,
+            }
+        }
+        Ok(self.module)
+    }
+
+    // Struct layout isn't wired up from `StructDeclaration` yet, so rather
+    // than hand out a bogus zero-field struct (wrong `alloca` size, wrong
+    // GEP base) this reports the gap honestly, the same way `Member`/
+    // `PointerMember` expressions already do in `lower_expr`.
+    fn llvm_type(&self, ty: &Type) -> CompilerResult<BasicTypeEnum<'ctx>, CodegenError> {
+        Ok(match ty {
+            Type::Void => self.context.i8_type().into(),
+            Type::Char { .. } => self.context.i8_type().into(),
+            Type::Int { .. } => self.context.i32_type().into(),
+            Type::Long { .. } => self.context.i64_type().into(),
+            Type::Double => self.context.f64_type().into(),
+            Type::Pointer(inner) => self.llvm_type(inner)?.ptr_type(Default::default()).into(),
+            Type::Array(inner, Some(size)) => self.llvm_type(inner)?.array_type(*size as u32).into(),
+            Type::Array(inner, None) => self.llvm_type(inner)?.ptr_type(Default::default()).into(),
+            Type::Struct(_) => return Err(CodegenError::Unsupported("struct layout")),
+            Type::Func { .. } => self.context.i64_type().into(),
+            Type::Var(_) => unreachable!("codegen only runs on fully-resolved HIR"),
+        })
+    }
+
+    fn declare_function(&mut self, func: &HirFunction) -> CompilerResult<(), CodegenError> {
+        let mut param_types = Vec::with_capacity(func.params.len());
+        for (_, ty) in &func.params {
+            param_types.push(BasicMetadataTypeEnum::from(self.llvm_type(ty)?));
+        }
+        let fn_type = if matches!(func.ret, Type::Void) {
+            self.context.void_type().fn_type(&param_types, false)
+        } else {
+            self.llvm_type(&func.ret)?.fn_type(&param_types, false)
+        };
+        let name = func.ident.to_string();
+        let function = self.module.add_function(&name, fn_type, None);
+        self.functions.insert(func.ident, function);
+        Ok(())
+    }
+
+    fn lower_function(&mut self, func: &HirFunction) -> CompilerResult<(), CodegenError> {
+        let function = self.functions[&func.ident];
+        let entry = self.context.append_basic_block(function, "entry");
+        self.builder.position_at_end(entry);
+        self.locals.push(HashMap::new());
+
+        for (i, (ident, ty)) in func.params.iter().enumerate() {
+            let param = function.get_nth_param(i as u32).unwrap();
+            let slot = self.builder.build_alloca(self.llvm_type(ty)?, &ident.to_string());
+            self.builder.build_store(slot, param);
+            self.locals.last_mut().unwrap().insert(*ident, slot);
+        }
+
+        for stmt in &func.body {
+            self.lower_statement(stmt, function)?;
+        }
+
+        if self.builder.get_insert_block().unwrap().get_terminator().is_none() {
+            if matches!(func.ret, Type::Void) {
+                self.builder.build_return(None);
+            } else {
+                let zero = self.llvm_type(&func.ret)?.const_zero();
+                self.builder.build_return(Some(&zero));
+            }
+        }
+
+        self.locals.pop();
+        Ok(())
+    }
+
+    fn lower_global(
+        &mut self,
+        ident: InternedStr,
+        ty: &Type,
+        init: Option<&HirExpr>,
+    ) -> CompilerResult<(), CodegenError> {
+        let llvm_ty = self.llvm_type(ty)?;
+        let global = self.module.add_global(llvm_ty, None, &ident.to_string());
+        if let Some(init) = init {
+            if let Some(constant) = const_basic_value(self, init) {
+                global.set_initializer(&constant);
+            }
+        } else {
+            global.set_initializer(&llvm_ty.const_zero());
+        }
+        self.globals.insert(ident, global.as_pointer_value());
+        Ok(())
+    }
+
+    fn lower_statement(
+        &mut self,
+        stmt: &HirStatement,
+        function: FunctionValue<'ctx>,
+    ) -> CompilerResult<(), CodegenError> {
+        match stmt {
+            HirStatement::Expression(expr) => {
+                self.lower_expr(expr)?;
+                Ok(())
+            }
+            HirStatement::Declaration(ident, ty, init) => {
+                let slot = self.builder.build_alloca(self.llvm_type(ty)?, &ident.to_string());
+                if let Some(init) = init {
+                    let value = self.lower_expr(init)?;
+                    self.builder.build_store(slot, value);
+                }
+                self.locals.last_mut().unwrap().insert(*ident, slot);
+                Ok(())
+            }
+            HirStatement::Block(stmts) => {
+                self.locals.push(HashMap::new());
+                for stmt in stmts {
+                    self.lower_statement(stmt, function)?;
+                }
+                self.locals.pop();
+                Ok(())
+            }
+            HirStatement::If(cond, then, otherwise) => {
+                let cond_value = self.lower_bool(cond)?;
+                let then_block = self.context.append_basic_block(function, "if.then");
+                let else_block = self.context.append_basic_block(function, "if.else");
+                let merge_block = self.context.append_basic_block(function, "if.merge");
+
+                self.builder.build_conditional_branch(cond_value, then_block, else_block);
+
+                self.builder.position_at_end(then_block);
+                self.lower_statement(then, function)?;
+                if self.builder.get_insert_block().unwrap().get_terminator().is_none() {
+                    self.builder.build_unconditional_branch(merge_block);
+                }
+
+                self.builder.position_at_end(else_block);
+                if let Some(otherwise) = otherwise {
+                    self.lower_statement(otherwise, function)?;
+                }
+                if self.builder.get_insert_block().unwrap().get_terminator().is_none() {
+                    self.builder.build_unconditional_branch(merge_block);
+                }
+
+                self.builder.position_at_end(merge_block);
+                Ok(())
+            }
+            HirStatement::While(cond, body) => {
+                let cond_block = self.context.append_basic_block(function, "while.cond");
+                let body_block = self.context.append_basic_block(function, "while.body");
+                let end_block = self.context.append_basic_block(function, "while.end");
+
+                self.builder.build_unconditional_branch(cond_block);
+                self.builder.position_at_end(cond_block);
+                let cond_value = self.lower_bool(cond)?;
+                self.builder.build_conditional_branch(cond_value, body_block, end_block);
+
+                self.builder.position_at_end(body_block);
+                self.loop_stack.push(LoopTargets { continue_block: cond_block, break_block: end_block });
+                self.lower_statement(body, function)?;
+                self.loop_stack.pop();
+                if self.builder.get_insert_block().unwrap().get_terminator().is_none() {
+                    self.builder.build_unconditional_branch(cond_block);
+                }
+
+                self.builder.position_at_end(end_block);
+                Ok(())
+            }
+            HirStatement::For(init, cond, step, body) => {
+                if let Some(init) = init {
+                    self.lower_statement(init, function)?;
+                }
+
+                let cond_block = self.context.append_basic_block(function, "for.cond");
+                let body_block = self.context.append_basic_block(function, "for.body");
+                let step_block = self.context.append_basic_block(function, "for.step");
+                let end_block = self.context.append_basic_block(function, "for.end");
+
+                self.builder.build_unconditional_branch(cond_block);
+                self.builder.position_at_end(cond_block);
+                let cond_value = match cond {
+                    Some(cond) => self.lower_bool(cond)?,
+                    None => self.context.bool_type().const_int(1, false),
+                };
+                self.builder.build_conditional_branch(cond_value, body_block, end_block);
+
+                // Unlike `While`, `continue` must target `step_block` rather
+                // than `cond_block` so the increment still runs before the
+                // condition is rechecked.
+                self.builder.position_at_end(body_block);
+                self.loop_stack.push(LoopTargets { continue_block: step_block, break_block: end_block });
+                self.lower_statement(body, function)?;
+                self.loop_stack.pop();
+                if self.builder.get_insert_block().unwrap().get_terminator().is_none() {
+                    self.builder.build_unconditional_branch(step_block);
+                }
+
+                self.builder.position_at_end(step_block);
+                if let Some(step) = step {
+                    self.lower_expr(step)?;
+                }
+                self.builder.build_unconditional_branch(cond_block);
+
+                self.builder.position_at_end(end_block);
+                Ok(())
+            }
+            HirStatement::Break => {
+                let target = self.loop_stack.last().expect("break outside loop").break_block;
+                self.builder.build_unconditional_branch(target);
+                Ok(())
+            }
+            HirStatement::Continue => {
+                let target = self.loop_stack.last().expect("continue outside loop").continue_block;
+                self.builder.build_unconditional_branch(target);
+                Ok(())
+            }
+            HirStatement::Return(expr) => {
+                match expr {
+                    Some(expr) => {
+                        let value = self.lower_expr(expr)?;
+                        self.builder.build_return(Some(&value));
+                    }
+                    None => {
+                        self.builder.build_return(None);
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn lower_bool(&mut self, expr: &HirExpr) -> CompilerResult<inkwell::values::IntValue<'ctx>, CodegenError> {
+        let value = self.lower_expr(expr)?;
+        Ok(match value {
+            BasicValueEnum::IntValue(v) => {
+                self.builder.build_int_compare(IntPredicate::NE, v, v.get_type().const_zero(), "tobool")
+            }
+            BasicValueEnum::FloatValue(v) => {
+                self.builder.build_float_compare(FloatPredicate::ONE, v, v.get_type().const_zero(), "tobool")
+            }
+            _ => return Err(CodegenError::Unsupported("non-scalar condition")),
+        })
+    }
+
+    fn lower_expr(&mut self, expr: &HirExpr) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        match &expr.kind {
+            HirExprKind::Literal(lit) => self.lower_literal(lit, &expr.ty),
+            HirExprKind::Variable(ident) => {
+                let slot = self.lookup(*ident)?;
+                Ok(self.builder.build_load(slot, &ident.to_string()))
+            }
+            HirExprKind::Coerce(inner, coercion) => self.lower_coercion(inner, coercion, &expr.ty),
+            HirExprKind::AddressOf(inner) => {
+                let ident = match &inner.kind {
+                    HirExprKind::Variable(ident) => *ident,
+                    _ => return Err(CodegenError::Unsupported("address-of a non-lvalue")),
+                };
+                Ok(self.lookup(ident)?.into())
+            }
+            HirExprKind::Deref(inner) => {
+                let ptr = self.lower_expr(inner)?.into_pointer_value();
+                Ok(self.builder.build_load(ptr, "deref"))
+            }
+            HirExprKind::Index(base, index) => {
+                let ptr = self.lower_expr(base)?.into_pointer_value();
+                let index = self.lower_expr(index)?.into_int_value();
+                let elem = unsafe { self.builder.build_gep(ptr, &[index], "index") };
+                Ok(self.builder.build_load(elem, "index.load"))
+            }
+            HirExprKind::Member(..) | HirExprKind::PointerMember(..) => {
+                Err(CodegenError::Unsupported("struct member access"))
+            }
+            HirExprKind::Unary(op, inner) => self.lower_unary(*op, inner),
+            HirExprKind::Binary(op, lhs, rhs) => self.lower_binary(op, lhs, rhs),
+            HirExprKind::Call(ident, args) => self.lower_call(*ident, args),
+        }
+    }
+
+    fn lookup(&self, ident: InternedStr) -> Result<PointerValue<'ctx>, CodegenError> {
+        for scope in self.locals.iter().rev() {
+            if let Some(slot) = scope.get(&ident) {
+                return Ok(*slot);
+            }
+        }
+        self.globals.get(&ident).copied().ok_or(CodegenError::UnknownVariable(ident))
+    }
+
+    fn lower_literal(&self, lit: &crate::tokens::Literal, ty: &Type) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        use crate::tokens::Literal::*;
+        Ok(match lit {
+            Integer(v) => self.llvm_type(ty)?.into_int_type().const_int(*v as u64, true).into(),
+            Float(v) => self.context.f64_type().const_float(*v).into(),
+            Char(c) => self.context.i8_type().const_int(*c as u64, false).into(),
+            Str(s) => self
+                .builder
+                .build_global_string_ptr(s.as_str(), "str")
+                .as_pointer_value()
+                .into(),
+        })
+    }
+
+    fn lower_coercion(
+        &mut self,
+        inner: &HirExpr,
+        coercion: &Coercion,
+        target: &Type,
+    ) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        let value = self.lower_expr(inner)?;
+        Ok(match coercion {
+            Coercion::IntegerPromotion => self
+                .builder
+                .build_int_s_extend(value.into_int_value(), self.llvm_type(target)?.into_int_type(), "promote")
+                .into(),
+            Coercion::Truncate => self
+                .builder
+                .build_int_truncate(value.into_int_value(), self.llvm_type(target)?.into_int_type(), "trunc")
+                .into(),
+            Coercion::ArithmeticConversion => match value {
+                BasicValueEnum::IntValue(v) => {
+                    self.builder.build_signed_int_to_float(v, self.context.f64_type(), "itof").into()
+                }
+                BasicValueEnum::FloatValue(v) => self
+                    .builder
+                    .build_float_to_signed_int(v, self.llvm_type(target)?.into_int_type(), "ftoi")
+                    .into(),
+                other => other,
+            },
+            Coercion::ArrayDecay | Coercion::PointerCast => value,
+        })
+    }
+
+    fn lower_unary(&mut self, op: crate::ast::UnaryOp, inner: &HirExpr) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        use crate::ast::UnaryOp::*;
+        let value = self.lower_expr(inner)?;
+        Ok(match (op, value) {
+            (Negate, BasicValueEnum::IntValue(v)) => self.builder.build_int_neg(v, "neg").into(),
+            (Negate, BasicValueEnum::FloatValue(v)) => self.builder.build_float_neg(v, "fneg").into(),
+            (BitwiseNot, BasicValueEnum::IntValue(v)) => self.builder.build_not(v, "not").into(),
+            (LogicalNot, BasicValueEnum::IntValue(v)) => {
+                let zero = v.get_type().const_zero();
+                let cmp = self.builder.build_int_compare(IntPredicate::EQ, v, zero, "lnot");
+                self.builder.build_int_z_extend(cmp, self.context.i32_type(), "lnot.ext").into()
+            }
+            (Plus, value) => value,
+            _ => return Err(CodegenError::Unsupported("unary operator on this operand type")),
+        })
+    }
+
+    fn lower_binary(
+        &mut self,
+        op: &crate::ast::BinaryOp,
+        lhs: &HirExpr,
+        rhs: &HirExpr,
+    ) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        use crate::ast::BinaryOp::*;
+        if let Assign(_) = op {
+            let ident = match &lhs.kind {
+                HirExprKind::Variable(ident) => *ident,
+                _ => return Err(CodegenError::Unsupported("assignment to a non-lvalue")),
+            };
+            let value = self.lower_expr(rhs)?;
+            let slot = self.lookup(ident)?;
+            self.builder.build_store(slot, value);
+            return Ok(value);
+        }
+
+        if let LogicalAnd | LogicalOr = op {
+            return self.lower_logical(op, lhs, rhs);
+        }
+
+        let lhs = self.lower_expr(lhs)?;
+        let rhs = self.lower_expr(rhs)?;
+        Ok(match (lhs, rhs) {
+            (BasicValueEnum::IntValue(l), BasicValueEnum::IntValue(r)) => match op {
+                Add => self.builder.build_int_add(l, r, "add").into(),
+                Subtract => self.builder.build_int_sub(l, r, "sub").into(),
+                Multiply => self.builder.build_int_mul(l, r, "mul").into(),
+                Divide => self.builder.build_int_signed_div(l, r, "div").into(),
+                Modulo => self.builder.build_int_signed_rem(l, r, "rem").into(),
+                BitwiseAnd => self.builder.build_and(l, r, "and").into(),
+                BitwiseOr => self.builder.build_or(l, r, "or").into(),
+                BitwiseXor => self.builder.build_xor(l, r, "xor").into(),
+                LeftShift => self.builder.build_left_shift(l, r, "shl").into(),
+                RightShift => self.builder.build_right_shift(l, r, true, "shr").into(),
+                Equal => self.bool_to_int(self.builder.build_int_compare(IntPredicate::EQ, l, r, "eq")),
+                NotEqual => self.bool_to_int(self.builder.build_int_compare(IntPredicate::NE, l, r, "ne")),
+                GreaterThan => self.bool_to_int(self.builder.build_int_compare(IntPredicate::SGT, l, r, "gt")),
+                GreaterThanEqual => self.bool_to_int(self.builder.build_int_compare(IntPredicate::SGE, l, r, "ge")),
+                LessThan => self.bool_to_int(self.builder.build_int_compare(IntPredicate::SLT, l, r, "lt")),
+                LessThanEqual => self.bool_to_int(self.builder.build_int_compare(IntPredicate::SLE, l, r, "le")),
+                LogicalAnd | LogicalOr => unreachable!("handled above"),
+                Assign(_) => unreachable!("handled above"),
+            },
+            (BasicValueEnum::FloatValue(l), BasicValueEnum::FloatValue(r)) => match op {
+                Add => self.builder.build_float_add(l, r, "fadd").into(),
+                Subtract => self.builder.build_float_sub(l, r, "fsub").into(),
+                Multiply => self.builder.build_float_mul(l, r, "fmul").into(),
+                Divide => self.builder.build_float_div(l, r, "fdiv").into(),
+                Equal => self.bool_to_int(self.builder.build_float_compare(FloatPredicate::OEQ, l, r, "feq")),
+                NotEqual => self.bool_to_int(self.builder.build_float_compare(FloatPredicate::ONE, l, r, "fne")),
+                GreaterThan => self.bool_to_int(self.builder.build_float_compare(FloatPredicate::OGT, l, r, "fgt")),
+                GreaterThanEqual => self.bool_to_int(self.builder.build_float_compare(FloatPredicate::OGE, l, r, "fge")),
+                LessThan => self.bool_to_int(self.builder.build_float_compare(FloatPredicate::OLT, l, r, "flt")),
+                LessThanEqual => self.bool_to_int(self.builder.build_float_compare(FloatPredicate::OLE, l, r, "fle")),
+                _ => return Err(CodegenError::Unsupported("operator not defined for floats")),
+            },
+            _ => return Err(CodegenError::Unsupported("binary operator on mismatched operand types")),
+        })
+    }
+
+    // `&&`/`||` must short-circuit and normalize each operand through its
+    // truthiness rather than evaluating both sides and combining the raw
+    // bits, so this builds conditional branches the same way `If`/`While`
+    // do instead of routing through the generic eager-eval path above.
+    fn lower_logical(
+        &mut self,
+        op: &crate::ast::BinaryOp,
+        lhs: &HirExpr,
+        rhs: &HirExpr,
+    ) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        use crate::ast::BinaryOp::*;
+        let function = self.builder.get_insert_block().unwrap().get_parent().unwrap();
+        let lhs_value = self.lower_bool(lhs)?;
+        let entry_block = self.builder.get_insert_block().unwrap();
+
+        let rhs_block = self.context.append_basic_block(function, "logical.rhs");
+        let merge_block = self.context.append_basic_block(function, "logical.merge");
+
+        match op {
+            LogicalAnd => self.builder.build_conditional_branch(lhs_value, rhs_block, merge_block),
+            LogicalOr => self.builder.build_conditional_branch(lhs_value, merge_block, rhs_block),
+            _ => unreachable!("lower_logical only handles LogicalAnd/LogicalOr"),
+        };
+
+        self.builder.position_at_end(rhs_block);
+        let rhs_value = self.lower_bool(rhs)?;
+        let rhs_end_block = self.builder.get_insert_block().unwrap();
+        self.builder.build_unconditional_branch(merge_block);
+
+        self.builder.position_at_end(merge_block);
+        let phi = self.builder.build_phi(self.context.bool_type(), "logical.phi");
+        phi.add_incoming(&[(&lhs_value, entry_block), (&rhs_value, rhs_end_block)]);
+
+        let result = phi.as_basic_value().into_int_value();
+        Ok(self.builder.build_int_z_extend(result, self.context.i32_type(), "logical.ext").into())
+    }
+
+    // Comparisons produce an `i1`, but the HIR always types a comparison's
+    // result as `int`, so every comparison gets widened here the same way
+    // `lower_unary`'s `LogicalNot` already widens its own `i1` result.
+    fn bool_to_int(&self, cmp: inkwell::values::IntValue<'ctx>) -> BasicValueEnum<'ctx> {
+        self.builder.build_int_z_extend(cmp, self.context.i32_type(), "cmp.ext").into()
+    }
+
+    fn lower_call(&mut self, ident: InternedStr, args: &[HirExpr]) -> CompilerResult<BasicValueEnum<'ctx>, CodegenError> {
+        let function = *self.functions.get(&ident).ok_or(CodegenError::UnknownFunction(ident))?;
+        let mut values = Vec::with_capacity(args.len());
+        for arg in args {
+            values.push(BasicMetadataValueEnum::from(self.lower_expr(arg)?));
+        }
+        let call = self.builder.build_call(function, &values, "call");
+        Ok(call
+            .try_as_basic_value()
+            .left()
+            .unwrap_or_else(|| self.context.i32_type().const_zero().into()))
+    }
+}
+
+fn const_basic_value<'ctx>(codegen: &Codegen<'ctx>, expr: &HirExpr) -> Option<BasicValueEnum<'ctx>> {
+    // Only non-string literal initializers are folded into a global's static
+    // initializer; any other initializer leaves the global without one.
+    match &expr.kind {
+        HirExprKind::Literal(lit) if !matches!(lit, crate::tokens::Literal::Str(_)) => {
+            codegen.lower_literal(lit, &expr.ty).ok()
+        }
+        _ => None,
+    }
+}
+
+pub fn compile(context: &Context, module_name: &str, decls: &[HirDeclaration]) -> CompilerResult<String, CodegenError> {
+    let codegen = Codegen::new(context, module_name);
+    let module = codegen.compile(decls)?;
+    Ok(module.print_to_string().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn llvm_type_maps_primitives_to_expected_widths() {
+        let context = Context::create();
+        let codegen = Codegen::new(&context, "test");
+        assert_eq!(codegen.llvm_type(&Type::Int { signed: true }).unwrap(), context.i32_type().into());
+        assert_eq!(codegen.llvm_type(&Type::Long { signed: true }).unwrap(), context.i64_type().into());
+        assert_eq!(codegen.llvm_type(&Type::Double).unwrap(), context.f64_type().into());
+        assert_eq!(codegen.llvm_type(&Type::Char { signed: true }).unwrap(), context.i8_type().into());
+    }
+
+    #[test]
+    fn llvm_type_reports_struct_as_unsupported_instead_of_a_bogus_empty_type() {
+        let context = Context::create();
+        let codegen = Codegen::new(&context, "test");
+        let ident = InternedStr::from("point");
+        assert!(matches!(codegen.llvm_type(&Type::Struct(ident)), Err(CodegenError::Unsupported(_))));
+    }
+}
@@ -0,0 +1,455 @@
+use crate::ast::{
+    ASTRoot, BinaryOp, Block, Declaration, DeclarationSpecifier, DeclaratorType, Expression,
+    InitDeclaration, Statement, TypeOrExpression, TypeQualifier, TypeSpecifier, UnaryOp,
+    VariableDeclaration,
+};
+use crate::str_intern::InternedStr;
+use crate::tokens::Literal;
+use std::collections::HashMap;
+
+#[derive(Debug)]
+pub enum FoldError {
+    DivisionByZero,
+    Overflow,
+    InvalidOperandType,
+}
+
+#[derive(Debug, Clone)]
+enum ConstValue {
+    Int(i64),
+    Double(f64),
+}
+
+impl ConstValue {
+    fn as_literal(&self) -> Literal {
+        match self {
+            ConstValue::Int(v) => Literal::Integer(*v),
+            ConstValue::Double(v) => Literal::Float(*v),
+        }
+    }
+
+    fn truthy(&self) -> bool {
+        match self {
+            ConstValue::Int(v) => *v != 0,
+            ConstValue::Double(v) => *v != 0.0,
+        }
+    }
+
+    fn from_literal(lit: &Literal) -> Option<ConstValue> {
+        match lit {
+            Literal::Integer(v) => Some(ConstValue::Int(*v)),
+            Literal::Float(v) => Some(ConstValue::Double(*v)),
+            Literal::Char(c) => Some(ConstValue::Int(*c as i64)),
+            Literal::Str(_) => None,
+        }
+    }
+}
+
+// Tracks which `const`-qualified locals currently have a known folded value,
+// one map per lexical scope so a shadowed or block-exited binding can't leak.
+#[derive(Default)]
+struct Scopes(Vec<HashMap<InternedStr, ConstValue>>);
+
+impl Scopes {
+    fn push(&mut self) {
+        self.0.push(HashMap::new());
+    }
+
+    fn pop(&mut self) {
+        self.0.pop();
+    }
+
+    fn bind(&mut self, ident: InternedStr, value: ConstValue) {
+        self.0.last_mut().unwrap().insert(ident, value);
+    }
+
+    // Mutation invalidates the binding in whichever scope currently holds it,
+    // so a later read never returns a value that was folded before the write.
+    fn invalidate(&mut self, ident: &InternedStr) {
+        for scope in self.0.iter_mut().rev() {
+            if scope.remove(ident).is_some() {
+                break;
+            }
+        }
+    }
+
+    fn get(&self, ident: &InternedStr) -> Option<ConstValue> {
+        self.0.iter().rev().find_map(|scope| scope.get(ident).cloned())
+    }
+}
+
+pub struct ConstantFolder {
+    scopes: Scopes,
+    diagnostics: Vec<FoldError>,
+}
+
+impl ConstantFolder {
+    pub fn new() -> Self {
+        ConstantFolder { scopes: Scopes(vec![HashMap::new()]), diagnostics: Vec::new() }
+    }
+
+    pub fn fold(mut self, ast: ASTRoot) -> (ASTRoot, Vec<FoldError>) {
+        let folded = ast.into_iter().map(|item| self.fold_init_declaration(item)).collect();
+        (folded, self.diagnostics)
+    }
+
+    fn fold_init_declaration(&mut self, item: InitDeclaration) -> InitDeclaration {
+        match item {
+            InitDeclaration::Declaration(decl) => InitDeclaration::Declaration(self.fold_variable_decl(decl)),
+            InitDeclaration::Function(mut func) => {
+                // A parameter shadows any file-scope binding of the same name.
+                for ident in func.parameters.iter().filter_map(|p| p.ident) {
+                    self.scopes.0.iter_mut().for_each(|scope| {
+                        scope.remove(&ident);
+                    });
+                }
+                if let Some(body) = func.body.take() {
+                    func.body = Some(self.fold_block(body));
+                }
+                InitDeclaration::Function(func)
+            }
+            other @ InitDeclaration::Struct(_) => other,
+        }
+    }
+
+    fn fold_variable_decl(&mut self, decl: VariableDeclaration) -> VariableDeclaration {
+        let VariableDeclaration { declaration, initializer } = decl;
+        let initializer = initializer.map(|e| self.fold_expr(e));
+        let value = if is_const(&declaration) { initializer.as_ref().and_then(const_value_of) } else { None };
+        if let Some(ident) = declaration.ident {
+            match value {
+                Some(value) => self.scopes.bind(ident, value),
+                // A declaration with no foldable value shadows outer bindings
+                // of the same name, so those are dropped from every scope.
+                None => self.scopes.0.iter_mut().for_each(|scope| {
+                    scope.remove(&ident);
+                }),
+            }
+        }
+        VariableDeclaration { declaration, initializer }
+    }
+
+    fn fold_block(&mut self, block: Block) -> Block {
+        self.scopes.push();
+        let stmts = block.0.into_iter().map(|s| self.fold_statement(s)).collect();
+        self.scopes.pop();
+        Block(stmts)
+    }
+
+    fn fold_statement(&mut self, stmt: Statement) -> Statement {
+        match stmt {
+            Statement::Expression(e) => Statement::Expression(self.fold_expr(e)),
+            Statement::Declaration(decl) => Statement::Declaration(self.fold_variable_decl(decl)),
+            Statement::If(cond, then, otherwise) => Statement::If(
+                self.fold_expr(cond),
+                Box::new(self.fold_statement(*then)),
+                otherwise.map(|s| Box::new(self.fold_statement(*s))),
+            ),
+            Statement::While(cond, body) => {
+                Statement::While(self.fold_expr(cond), Box::new(self.fold_statement(*body)))
+            }
+            Statement::For(init, cond, step, body) => Statement::For(
+                init.map(|d| self.fold_variable_decl(d)),
+                cond.map(|e| self.fold_expr(e)),
+                step.map(|e| self.fold_expr(e)),
+                Box::new(self.fold_statement(*body)),
+            ),
+            Statement::Break => Statement::Break,
+            Statement::Continue => Statement::Continue,
+            Statement::Return(expr) => Statement::Return(expr.map(|e| self.fold_expr(e))),
+            Statement::Block(block) => Statement::Block(self.fold_block(block)),
+        }
+    }
+
+    fn fold_expr(&mut self, expr: Expression) -> Expression {
+        match expr {
+            Expression::Parenthesized(inner) => self.fold_expr(*inner),
+            Expression::Variable(ident) => match self.scopes.get(&ident) {
+                Some(value) => Expression::Literal(value.as_literal()),
+                None => Expression::Variable(ident),
+            },
+            Expression::Sizeof(TypeOrExpression::Type(decl)) => {
+                match known_size_of(&decl) {
+                    Some(size) => Expression::Literal(Literal::Integer(size as i64)),
+                    None => Expression::Sizeof(TypeOrExpression::Type(decl)),
+                }
+            }
+            Expression::Sizeof(TypeOrExpression::Expr(inner)) => {
+                Expression::Sizeof(TypeOrExpression::Expr(Box::new(self.fold_expr(*inner))))
+            }
+            Expression::Unary(op @ (UnaryOp::Increment | UnaryOp::Decrement), inner) => {
+                if let Expression::Variable(ident) = inner.as_ref() {
+                    self.scopes.invalidate(ident);
+                }
+                Expression::Unary(op, inner)
+            }
+            Expression::Unary(op, inner) => {
+                let inner = self.fold_expr(*inner);
+                match (&op, const_value_of(&inner)) {
+                    (UnaryOp::Negate, Some(v)) => Expression::Literal(negate(v).as_literal()),
+                    (UnaryOp::LogicalNot, Some(v)) => {
+                        Expression::Literal(Literal::Integer(!v.truthy() as i64))
+                    }
+                    (UnaryOp::BitwiseNot, Some(ConstValue::Int(v))) => {
+                        Expression::Literal(Literal::Integer(!v))
+                    }
+                    (UnaryOp::Plus, Some(v)) => Expression::Literal(v.as_literal()),
+                    _ => Expression::Unary(op, Box::new(inner)),
+                }
+            }
+            Expression::Binary(BinaryOp::LogicalAnd, lhs, rhs) => {
+                let lhs = self.fold_expr(*lhs);
+                if let Some(value) = const_value_of(&lhs) {
+                    if !value.truthy() {
+                        return Expression::Literal(Literal::Integer(0));
+                    }
+                    let rhs = self.fold_expr(*rhs);
+                    return match const_value_of(&rhs) {
+                        Some(v) => Expression::Literal(Literal::Integer(v.truthy() as i64)),
+                        None => Expression::Binary(BinaryOp::LogicalAnd, Box::new(lhs), Box::new(rhs)),
+                    };
+                }
+                let rhs = self.fold_expr(*rhs);
+                Expression::Binary(BinaryOp::LogicalAnd, Box::new(lhs), Box::new(rhs))
+            }
+            Expression::Binary(BinaryOp::LogicalOr, lhs, rhs) => {
+                let lhs = self.fold_expr(*lhs);
+                if let Some(value) = const_value_of(&lhs) {
+                    if value.truthy() {
+                        return Expression::Literal(Literal::Integer(1));
+                    }
+                    let rhs = self.fold_expr(*rhs);
+                    return match const_value_of(&rhs) {
+                        Some(v) => Expression::Literal(Literal::Integer(v.truthy() as i64)),
+                        None => Expression::Binary(BinaryOp::LogicalOr, Box::new(lhs), Box::new(rhs)),
+                    };
+                }
+                let rhs = self.fold_expr(*rhs);
+                Expression::Binary(BinaryOp::LogicalOr, Box::new(lhs), Box::new(rhs))
+            }
+            Expression::Binary(BinaryOp::Assign(op), lhs, rhs) => {
+                if let Expression::Variable(ident) = lhs.as_ref() {
+                    self.scopes.invalidate(ident);
+                }
+                Expression::Binary(BinaryOp::Assign(op), lhs, Box::new(self.fold_expr(*rhs)))
+            }
+            Expression::Binary(op, lhs, rhs) => {
+                let lhs = self.fold_expr(*lhs);
+                let rhs = self.fold_expr(*rhs);
+                match (const_value_of(&lhs), const_value_of(&rhs)) {
+                    (Some(l), Some(r)) => match fold_arithmetic(&op, l, r) {
+                        Ok(value) => Expression::Literal(value.as_literal()),
+                        Err(err) => {
+                            self.diagnostics.push(err);
+                            Expression::Binary(op, Box::new(lhs), Box::new(rhs))
+                        }
+                    },
+                    _ => Expression::Binary(op, Box::new(lhs), Box::new(rhs)),
+                }
+            }
+            Expression::PostFix(op, inner) => {
+                if let Expression::Variable(ident) = inner.as_ref() {
+                    self.scopes.invalidate(ident);
+                }
+                Expression::PostFix(op, inner)
+            }
+            Expression::FunctionCall(ident, args) => {
+                Expression::FunctionCall(ident, args.into_iter().map(|a| self.fold_expr(a)).collect())
+            }
+            Expression::Index(base, index) => {
+                Expression::Index(Box::new(self.fold_expr(*base)), Box::new(self.fold_expr(*index)))
+            }
+            Expression::Member(base, field) => Expression::Member(Box::new(self.fold_expr(*base)), field),
+            Expression::PointerMember(base, field) => {
+                Expression::PointerMember(Box::new(self.fold_expr(*base)), field)
+            }
+            Expression::Cast(spec, inner) => Expression::Cast(spec, Box::new(self.fold_expr(*inner))),
+            literal @ Expression::Literal(_) => literal,
+        }
+    }
+}
+
+fn is_const(decl: &Declaration) -> bool {
+    decl.specifier.qualifiers.iter().any(|q| matches!(q, TypeQualifier::Const))
+}
+
+fn const_value_of(expr: &Expression) -> Option<ConstValue> {
+    match expr {
+        Expression::Literal(lit) => ConstValue::from_literal(lit),
+        _ => None,
+    }
+}
+
+fn negate(value: ConstValue) -> ConstValue {
+    match value {
+        ConstValue::Int(v) => ConstValue::Int(-v),
+        ConstValue::Double(v) => ConstValue::Double(-v),
+    }
+}
+
+fn known_size_of(decl: &Declaration) -> Option<usize> {
+    // Pointer/array sizes would need the HIR/codegen stage's type layout;
+    // bare primitives don't, so those are the only shapes folded here.
+    if !matches!(decl.declarator, DeclaratorType::None) {
+        return None;
+    }
+    primitive_size(&decl.specifier.ty)
+}
+
+fn primitive_size(specifiers: &[TypeSpecifier]) -> Option<usize> {
+    use TypeSpecifier::*;
+    specifiers.iter().find_map(|ty| match ty {
+        Void => Some(1),
+        Char => Some(1),
+        Int => Some(4),
+        Long => Some(8),
+        Double => Some(8),
+        Signed | Unsigned | Struct(_) => None,
+    })
+}
+
+fn fold_arithmetic(op: &BinaryOp, lhs: ConstValue, rhs: ConstValue) -> Result<ConstValue, FoldError> {
+    use BinaryOp::*;
+    if matches!(op, Divide | Modulo) && matches!(rhs, ConstValue::Int(0)) {
+        return Err(FoldError::DivisionByZero);
+    }
+    match (lhs, rhs) {
+        (ConstValue::Int(l), ConstValue::Int(r)) => apply_int(op, l, r),
+        (l, r) => apply_double(op, as_f64(l), as_f64(r)),
+    }
+}
+
+fn as_f64(value: ConstValue) -> f64 {
+    match value {
+        ConstValue::Int(v) => v as f64,
+        ConstValue::Double(v) => v,
+    }
+}
+
+fn apply_double(op: &BinaryOp, l: f64, r: f64) -> Result<ConstValue, FoldError> {
+    use BinaryOp::*;
+    Ok(match op {
+        Add => ConstValue::Double(l + r),
+        Subtract => ConstValue::Double(l - r),
+        Multiply => ConstValue::Double(l * r),
+        Divide => ConstValue::Double(l / r),
+        Equal => ConstValue::Int((l == r) as i64),
+        NotEqual => ConstValue::Int((l != r) as i64),
+        GreaterThan => ConstValue::Int((l > r) as i64),
+        GreaterThanEqual => ConstValue::Int((l >= r) as i64),
+        LessThan => ConstValue::Int((l < r) as i64),
+        LessThanEqual => ConstValue::Int((l <= r) as i64),
+        // Modulo/bitwise/shift ops aren't defined on `double` in C; such
+        // input is already invalid, but we still fail the fold rather than
+        // silently handing back the left operand unchanged.
+        Modulo | BitwiseAnd | BitwiseOr | BitwiseXor | LeftShift | RightShift | LogicalAnd
+        | LogicalOr | Assign(_) => return Err(FoldError::InvalidOperandType),
+    })
+}
+
+fn apply_int(op: &BinaryOp, l: i64, r: i64) -> Result<ConstValue, FoldError> {
+    use BinaryOp::*;
+    Ok(match op {
+        Add => ConstValue::Int(l.wrapping_add(r)),
+        Subtract => ConstValue::Int(l.wrapping_sub(r)),
+        Multiply => ConstValue::Int(l.wrapping_mul(r)),
+        Divide => {
+            // `i64::MIN / -1` overflows the representable range and panics
+            // unconditionally (release builds included), so this is reported
+            // the same way division by zero already is rather than aborting.
+            if l == i64::MIN && r == -1 {
+                return Err(FoldError::Overflow);
+            }
+            ConstValue::Int(l / r)
+        }
+        Modulo => {
+            if l == i64::MIN && r == -1 {
+                return Err(FoldError::Overflow);
+            }
+            ConstValue::Int(l % r)
+        }
+        Equal => ConstValue::Int((l == r) as i64),
+        NotEqual => ConstValue::Int((l != r) as i64),
+        GreaterThan => ConstValue::Int((l > r) as i64),
+        GreaterThanEqual => ConstValue::Int((l >= r) as i64),
+        LessThan => ConstValue::Int((l < r) as i64),
+        LessThanEqual => ConstValue::Int((l <= r) as i64),
+        BitwiseAnd => ConstValue::Int(l & r),
+        BitwiseOr => ConstValue::Int(l | r),
+        BitwiseXor => ConstValue::Int(l ^ r),
+        LeftShift => {
+            // A shift amount outside `0..64` panics in debug builds (and is
+            // UB either way), so it's reported as a fold error instead of
+            // folding `1 << 64` into a crash.
+            if !(0..64).contains(&r) {
+                return Err(FoldError::Overflow);
+            }
+            ConstValue::Int(l << r)
+        }
+        RightShift => {
+            if !(0..64).contains(&r) {
+                return Err(FoldError::Overflow);
+            }
+            ConstValue::Int(l >> r)
+        }
+        LogicalAnd => ConstValue::Int(((l != 0) && (r != 0)) as i64),
+        LogicalOr => ConstValue::Int(((l != 0) || (r != 0)) as i64),
+        Assign(_) => ConstValue::Int(r),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn primitive_decl(ty: TypeSpecifier) -> Declaration {
+        Declaration {
+            specifier: DeclarationSpecifier { specifiers: vec![], qualifiers: vec![], ty: vec![ty] },
+            declarator: DeclaratorType::None,
+            ident: None,
+        }
+    }
+
+    #[test]
+    fn sizeof_primitives_fold_without_layout_info() {
+        assert_eq!(known_size_of(&primitive_decl(TypeSpecifier::Char)), Some(1));
+        assert_eq!(known_size_of(&primitive_decl(TypeSpecifier::Int)), Some(4));
+        assert_eq!(known_size_of(&primitive_decl(TypeSpecifier::Long)), Some(8));
+        assert_eq!(known_size_of(&primitive_decl(TypeSpecifier::Double)), Some(8));
+    }
+
+    #[test]
+    fn sizeof_pointer_is_left_unfolded() {
+        let mut decl = primitive_decl(TypeSpecifier::Int);
+        decl.declarator = DeclaratorType::Pointer { to: Box::new(DeclaratorType::None) };
+        assert_eq!(known_size_of(&decl), None);
+    }
+
+    #[test]
+    fn folds_simple_integer_arithmetic() {
+        let mut folder = ConstantFolder::new();
+        let expr = folder.fold_expr(Expression::Binary(
+            BinaryOp::Add,
+            Box::new(Expression::Literal(Literal::Integer(2))),
+            Box::new(Expression::Literal(Literal::Integer(3))),
+        ));
+        assert!(matches!(expr, Expression::Literal(Literal::Integer(5))));
+    }
+
+    #[test]
+    fn division_by_zero_is_reported_and_left_unfolded() {
+        let mut folder = ConstantFolder::new();
+        let expr = folder.fold_expr(Expression::Binary(
+            BinaryOp::Divide,
+            Box::new(Expression::Literal(Literal::Integer(1))),
+            Box::new(Expression::Literal(Literal::Integer(0))),
+        ));
+        assert!(matches!(expr, Expression::Binary(BinaryOp::Divide, ..)));
+        assert!(matches!(folder.diagnostics.as_slice(), [FoldError::DivisionByZero]));
+    }
+
+    #[test]
+    fn apply_double_rejects_bitwise_op() {
+        assert!(matches!(apply_double(&BinaryOp::BitwiseAnd, 1.0, 2.0), Err(FoldError::InvalidOperandType)));
+    }
+}
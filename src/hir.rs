@@ -0,0 +1,721 @@
+use crate::ast::{
+    ASTRoot, AssignOp, BinaryOp, Block, Declaration, DeclarationSpecifier, DeclaratorType,
+    Expression, FunctionDeclaration, InitDeclaration, Statement, TypeSpecifier, UnaryOp,
+    VariableDeclaration,
+};
+use crate::str_intern::InternedStr;
+use crate::util::CompilerResult;
+use std::collections::HashMap;
+
+// Fully resolved or still-unknown type. `Var` only ever appears while a
+// function body is being inferred; by the time `lower` returns every `Var`
+// has been substituted away or reported as an error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Type {
+    Void,
+    Char { signed: bool },
+    Int { signed: bool },
+    Long { signed: bool },
+    Double,
+    Pointer(Box<Type>),
+    Array(Box<Type>, Option<usize>),
+    Struct(InternedStr),
+    Func { ret: Box<Type>, params: Vec<Type> },
+    Var(u32),
+}
+
+impl Type {
+    fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Type::Char { .. } | Type::Int { .. } | Type::Long { .. }
+        )
+    }
+
+    fn is_arithmetic(&self) -> bool {
+        self.is_integer() || matches!(self, Type::Double)
+    }
+}
+
+#[derive(Debug)]
+pub enum TypeError {
+    Mismatch { expected: Type, found: Type },
+    OccursCheck { var: u32, ty: Type },
+    Unresolved(u32),
+    UnknownIdent(InternedStr),
+    NotIndexable(Type),
+    NotDereferenceable(Type),
+    NotCallable(InternedStr),
+}
+
+// Coercions the HIR makes explicit so later passes never have to re-derive
+// C's implicit conversions from bare unification.
+#[derive(Debug, Clone)]
+pub enum Coercion {
+    IntegerPromotion,
+    Truncate,
+    ArithmeticConversion,
+    ArrayDecay,
+    PointerCast,
+}
+
+#[derive(Debug)]
+pub struct HirExpr {
+    pub kind: HirExprKind,
+    pub ty: Type,
+}
+
+#[derive(Debug)]
+pub enum HirExprKind {
+    Literal(crate::tokens::Literal),
+    Variable(InternedStr),
+    Unary(UnaryOp, Box<HirExpr>),
+    Binary(BinaryOp, Box<HirExpr>, Box<HirExpr>),
+    Call(InternedStr, Vec<HirExpr>),
+    Index(Box<HirExpr>, Box<HirExpr>),
+    Member(Box<HirExpr>, InternedStr),
+    PointerMember(Box<HirExpr>, InternedStr),
+    Deref(Box<HirExpr>),
+    AddressOf(Box<HirExpr>),
+    Coerce(Box<HirExpr>, Coercion),
+}
+
+#[derive(Debug)]
+pub enum HirStatement {
+    Expression(HirExpr),
+    Declaration(InternedStr, Type, Option<HirExpr>),
+    If(HirExpr, Box<HirStatement>, Option<Box<HirStatement>>),
+    While(HirExpr, Box<HirStatement>),
+    // Kept distinct from `While` rather than desugared into it: `continue`
+    // inside a `for` body must still run `step` before rechecking `cond`,
+    // which means its continue target is the step, not the condition block
+    // `While`'s continue target reuses.
+    For(Option<Box<HirStatement>>, Option<HirExpr>, Option<HirExpr>, Box<HirStatement>),
+    Return(Option<HirExpr>),
+    Break,
+    Continue,
+    Block(Vec<HirStatement>),
+}
+
+#[derive(Debug)]
+pub struct HirFunction {
+    pub ident: InternedStr,
+    pub params: Vec<(InternedStr, Type)>,
+    pub ret: Type,
+    pub body: Vec<HirStatement>,
+}
+
+#[derive(Debug)]
+pub enum HirDeclaration {
+    Function(HirFunction),
+    Global(InternedStr, Type, Option<HirExpr>),
+}
+
+// Union-find-style substitution from type variable id to the type it's
+// bound to. Lookup follows chains of variables to their representative.
+#[derive(Debug, Default)]
+struct Subst(HashMap<u32, Type>);
+
+impl Subst {
+    fn resolve(&self, ty: &Type) -> Type {
+        match ty {
+            Type::Var(id) => match self.0.get(id) {
+                Some(bound) => self.resolve(bound),
+                None => Type::Var(*id),
+            },
+            Type::Pointer(inner) => Type::Pointer(Box::new(self.resolve(inner))),
+            Type::Array(inner, size) => Type::Array(Box::new(self.resolve(inner)), *size),
+            Type::Func { ret, params } => Type::Func {
+                ret: Box::new(self.resolve(ret)),
+                params: params.iter().map(|p| self.resolve(p)).collect(),
+            },
+            other => other.clone(),
+        }
+    }
+
+    fn bind(&mut self, var: u32, ty: Type) -> Result<(), TypeError> {
+        if occurs(var, &ty) {
+            return Err(TypeError::OccursCheck { var, ty });
+        }
+        self.0.insert(var, ty);
+        Ok(())
+    }
+}
+
+fn occurs(var: u32, ty: &Type) -> bool {
+    match ty {
+        Type::Var(id) => *id == var,
+        Type::Pointer(inner) | Type::Array(inner, _) => occurs(var, inner),
+        Type::Func { ret, params } => occurs(var, ret) || params.iter().any(|p| occurs(var, p)),
+        _ => false,
+    }
+}
+
+fn unify(subst: &mut Subst, a: &Type, b: &Type) -> Result<Type, TypeError> {
+    let a = subst.resolve(a);
+    let b = subst.resolve(b);
+    match (&a, &b) {
+        (Type::Var(x), Type::Var(y)) if x == y => Ok(a),
+        (Type::Var(x), _) => {
+            subst.bind(*x, b.clone())?;
+            Ok(b)
+        }
+        (_, Type::Var(y)) => {
+            subst.bind(*y, a.clone())?;
+            Ok(a)
+        }
+        (Type::Pointer(l), Type::Pointer(r)) => {
+            Ok(Type::Pointer(Box::new(unify(subst, l, r)?)))
+        }
+        (Type::Array(l, ls), Type::Array(r, rs)) => {
+            let elem = unify(subst, l, r)?;
+            Ok(Type::Array(Box::new(elem), ls.or(*rs)))
+        }
+        (Type::Func { ret: lr, params: lp }, Type::Func { ret: rr, params: rp })
+            if lp.len() == rp.len() =>
+        {
+            let ret = unify(subst, lr, rr)?;
+            let params = lp
+                .iter()
+                .zip(rp.iter())
+                .map(|(l, r)| unify(subst, l, r))
+                .collect::<Result<_, _>>()?;
+            Ok(Type::Func { ret: Box::new(ret), params })
+        }
+        _ if a == b => Ok(a),
+        _ => Err(TypeError::Mismatch { expected: a, found: b }),
+    }
+}
+
+struct Lowering {
+    subst: Subst,
+    next_var: u32,
+    scopes: Vec<HashMap<InternedStr, Type>>,
+}
+
+impl Lowering {
+    fn new() -> Self {
+        Lowering { subst: Subst::default(), next_var: 0, scopes: vec![HashMap::new()] }
+    }
+
+    fn fresh(&mut self) -> Type {
+        let var = self.next_var;
+        self.next_var += 1;
+        Type::Var(var)
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn bind(&mut self, ident: InternedStr, ty: Type) {
+        self.scopes.last_mut().unwrap().insert(ident, ty);
+    }
+
+    fn lookup(&self, ident: &InternedStr) -> Option<Type> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(ident).cloned())
+    }
+
+    fn resolve_declarator(&mut self, specifier: &DeclarationSpecifier, declarator: &DeclaratorType) -> Type {
+        let base = resolve_specifier(specifier);
+        self.resolve_declarator_around(base, declarator)
+    }
+
+    fn resolve_declarator_around(&mut self, base: Type, declarator: &DeclaratorType) -> Type {
+        match declarator {
+            DeclaratorType::None => base,
+            DeclaratorType::Pointer { to } => {
+                Type::Pointer(Box::new(self.resolve_declarator_around(base, to)))
+            }
+            DeclaratorType::Array { of, size } => {
+                let inner = self.resolve_declarator_around(base, of);
+                match size {
+                    Some(n) => Type::Array(Box::new(inner), Some(*n)),
+                    None => Type::Array(Box::new(inner), None),
+                }
+            }
+        }
+    }
+
+    fn lower_function(&mut self, func: &FunctionDeclaration) -> Result<HirFunction, TypeError> {
+        let ret = self.resolve_declarator(&func.declaration.specifier, &func.declaration.declarator);
+        let ident = func.declaration.ident.expect("function declaration must be named");
+
+        self.push_scope();
+        let mut params = Vec::with_capacity(func.parameters.len());
+        for param in &func.parameters {
+            let ty = self.resolve_declarator(&param.specifier, &param.declarator);
+            if let Some(name) = param.ident {
+                self.bind(name, ty.clone());
+                params.push((name, ty));
+            }
+        }
+
+        let body = match &func.body {
+            Some(block) => self.lower_block(block, &ret)?,
+            None => Vec::new(),
+        };
+        self.pop_scope();
+
+        let ret = self.subst.resolve(&ret);
+        let params = params
+            .into_iter()
+            .map(|(name, ty)| (name, self.subst.resolve(&ty)))
+            .collect();
+        Ok(HirFunction { ident, params, ret, body })
+    }
+
+    fn lower_block(&mut self, block: &Block, ret_ty: &Type) -> Result<Vec<HirStatement>, TypeError> {
+        self.push_scope();
+        let result = block.0.iter().map(|s| self.lower_statement(s, ret_ty)).collect();
+        self.pop_scope();
+        result
+    }
+
+    fn lower_variable_declaration(&mut self, decl: &VariableDeclaration) -> Result<HirStatement, TypeError> {
+        let ty = self.resolve_declarator(&decl.declaration.specifier, &decl.declaration.declarator);
+        let ident = decl.declaration.ident.expect("variable declaration must be named");
+        let init = match &decl.initializer {
+            Some(expr) => {
+                let hir = self.lower_expr(expr)?;
+                let hir_ty = unify(&mut self.subst, &ty, &hir.ty)?;
+                Some(coerce(hir, &ty, &hir_ty))
+            }
+            None => None,
+        };
+        self.bind(ident, ty.clone());
+        Ok(HirStatement::Declaration(ident, ty, init))
+    }
+
+    fn lower_statement(&mut self, stmt: &Statement, ret_ty: &Type) -> Result<HirStatement, TypeError> {
+        Ok(match stmt {
+            Statement::Expression(e) => HirStatement::Expression(self.lower_expr(e)?),
+            Statement::Declaration(decl) => self.lower_variable_declaration(decl)?,
+            Statement::If(cond, then, otherwise) => HirStatement::If(
+                self.lower_expr(cond)?,
+                Box::new(self.lower_statement(then, ret_ty)?),
+                otherwise
+                    .as_ref()
+                    .map(|s| self.lower_statement(s, ret_ty))
+                    .transpose()?
+                    .map(Box::new),
+            ),
+            Statement::While(cond, body) => {
+                HirStatement::While(self.lower_expr(cond)?, Box::new(self.lower_statement(body, ret_ty)?))
+            }
+            Statement::For(init, cond, step, body) => {
+                self.push_scope();
+                let init = init
+                    .as_ref()
+                    .map(|decl| self.lower_variable_declaration(decl))
+                    .transpose()?
+                    .map(Box::new);
+                let cond = cond.as_ref().map(|c| self.lower_expr(c)).transpose()?;
+                let step = step.as_ref().map(|s| self.lower_expr(s)).transpose()?;
+                let body = Box::new(self.lower_statement(body, ret_ty)?);
+                self.pop_scope();
+                HirStatement::For(init, cond, step, body)
+            }
+            Statement::Break => HirStatement::Break,
+            Statement::Continue => HirStatement::Continue,
+            Statement::Return(expr) => HirStatement::Return(match expr {
+                Some(e) => {
+                    let hir = self.lower_expr(e)?;
+                    let resolved = unify(&mut self.subst, ret_ty, &hir.ty)?;
+                    Some(coerce(hir, ret_ty, &resolved))
+                }
+                None => None,
+            }),
+            Statement::Block(block) => HirStatement::Block(self.lower_block(block, ret_ty)?),
+        })
+    }
+
+    fn lower_expr(&mut self, expr: &Expression) -> Result<HirExpr, TypeError> {
+        match expr {
+            Expression::Literal(lit) => Ok(HirExpr { kind: HirExprKind::Literal(lit.clone()), ty: literal_type(lit) }),
+            Expression::Variable(ident) => {
+                let ty = self.lookup(ident).ok_or(TypeError::UnknownIdent(*ident))?;
+                Ok(HirExpr { kind: HirExprKind::Variable(*ident), ty })
+            }
+            Expression::Parenthesized(inner) => self.lower_expr(inner),
+            Expression::Sizeof(_) => {
+                Ok(HirExpr { kind: HirExprKind::Literal(crate::tokens::Literal::Integer(0)), ty: Type::Long { signed: false } })
+            }
+            Expression::PostFix(_, inner) | Expression::Unary(UnaryOp::Increment | UnaryOp::Decrement, inner) => {
+                self.lower_expr(inner)
+            }
+            Expression::Unary(UnaryOp::Deref, inner) => {
+                let hir = self.lower_expr(inner)?;
+                let pointee = self.fresh();
+                let resolved = unify(&mut self.subst, &hir.ty, &Type::Pointer(Box::new(pointee.clone())))?;
+                let pointee = match resolved {
+                    Type::Pointer(inner) => *inner,
+                    other => return Err(TypeError::NotDereferenceable(other)),
+                };
+                Ok(HirExpr { kind: HirExprKind::Deref(Box::new(hir)), ty: pointee })
+            }
+            Expression::Unary(UnaryOp::AddressOf, inner) => {
+                let hir = self.lower_expr(inner)?;
+                let ty = Type::Pointer(Box::new(hir.ty.clone()));
+                Ok(HirExpr { kind: HirExprKind::AddressOf(Box::new(hir)), ty })
+            }
+            Expression::Unary(op, inner) => {
+                let hir = self.lower_expr(inner)?;
+                // `!x` is an `int` in C whatever the operand's type.
+                let ty = if matches!(op, UnaryOp::LogicalNot) { Type::Int { signed: true } } else { hir.ty.clone() };
+                Ok(HirExpr { kind: HirExprKind::Unary(*op, Box::new(hir)), ty })
+            }
+            Expression::Binary(BinaryOp::Assign(AssignOp::Assign), lhs, rhs) => {
+                let lhs = self.lower_expr(lhs)?;
+                let rhs = self.lower_expr(rhs)?;
+                let resolved = unify(&mut self.subst, &lhs.ty, &rhs.ty)?;
+                let rhs = coerce(rhs, &lhs.ty, &resolved);
+                let ty = lhs.ty.clone();
+                Ok(HirExpr { kind: HirExprKind::Binary(BinaryOp::Assign(AssignOp::Assign), Box::new(lhs), Box::new(rhs)), ty })
+            }
+            // Compound assignment desugars to `a = a <op> b`: the target is
+            // lowered twice (once to read, once to store into) since HirExpr
+            // isn't Clone, and the arithmetic half goes through the usual
+            // arithmetic conversions just like a plain binary op would.
+            Expression::Binary(BinaryOp::Assign(assign_op), lhs, rhs) => {
+                let target = self.lower_expr(lhs)?;
+                let read = self.lower_expr(lhs)?;
+                let rhs = self.lower_expr(rhs)?;
+                let (read, rhs, result_ty) = self.usual_arithmetic_conversions(read, rhs)?;
+                let combined = HirExpr {
+                    kind: HirExprKind::Binary(desugar_assign_op(assign_op), Box::new(read), Box::new(rhs)),
+                    ty: result_ty,
+                };
+                let resolved = unify(&mut self.subst, &target.ty, &combined.ty)?;
+                let value = coerce(combined, &target.ty, &resolved);
+                let ty = target.ty.clone();
+                Ok(HirExpr { kind: HirExprKind::Binary(BinaryOp::Assign(AssignOp::Assign), Box::new(target), Box::new(value)), ty })
+            }
+            Expression::Binary(op, lhs, rhs) => {
+                let lhs = self.lower_expr(lhs)?;
+                let rhs = self.lower_expr(rhs)?;
+                let (lhs, rhs, ty) = self.usual_arithmetic_conversions(lhs, rhs)?;
+                // Relational/equality and logical operators convert their
+                // operands like any other binary op, but the *result* is
+                // always `int` in C regardless of the operand type — unlike
+                // arithmetic ops, whose result keeps the promoted operand type.
+                let ty = if is_comparison_or_logical(op) { Type::Int { signed: true } } else { ty };
+                Ok(HirExpr { kind: HirExprKind::Binary(clone_binary_op(op), Box::new(lhs), Box::new(rhs)), ty })
+            }
+            Expression::FunctionCall(ident, args) => {
+                let func_ty = self.lookup(ident).ok_or(TypeError::NotCallable(*ident))?;
+                let (ret, params) = match func_ty {
+                    Type::Func { ret, params } => (*ret, params),
+                    other => return Err(TypeError::Mismatch { expected: Type::Func { ret: Box::new(Type::Void), params: vec![] }, found: other }),
+                };
+                let mut hir_args = Vec::with_capacity(args.len());
+                for (arg, param_ty) in args.iter().zip(params.iter()) {
+                    let hir = self.lower_expr(arg)?;
+                    let resolved = unify(&mut self.subst, param_ty, &hir.ty)?;
+                    hir_args.push(coerce(hir, param_ty, &resolved));
+                }
+                for arg in args.iter().skip(params.len()) {
+                    hir_args.push(self.lower_expr(arg)?);
+                }
+                Ok(HirExpr { kind: HirExprKind::Call(*ident, hir_args), ty: ret })
+            }
+            Expression::Index(base, index) => {
+                let base = self.lower_expr(base)?;
+                let index = self.lower_expr(index)?;
+                let elem = self.fresh();
+                let array_ty = Type::Array(Box::new(elem.clone()), None);
+                let resolved = match unify(&mut self.subst, &base.ty, &array_ty) {
+                    Ok(t) => t,
+                    Err(_) => unify(&mut self.subst, &base.ty, &Type::Pointer(Box::new(elem.clone())))?,
+                };
+                let elem = match resolved {
+                    Type::Array(inner, _) | Type::Pointer(inner) => *inner,
+                    other => return Err(TypeError::NotIndexable(other)),
+                };
+                let base = decay(base);
+                Ok(HirExpr { kind: HirExprKind::Index(Box::new(base), Box::new(index)), ty: elem })
+            }
+            // There's no struct symbol table yet to look up the field's real
+            // type against, so rather than hand codegen a naked, never-unified
+            // type variable we report it as unresolved until struct field
+            // typing exists.
+            Expression::Member(base, _field) => {
+                self.lower_expr(base)?;
+                let var = match self.fresh() {
+                    Type::Var(id) => id,
+                    _ => unreachable!("fresh() always produces Type::Var"),
+                };
+                Err(TypeError::Unresolved(var))
+            }
+            Expression::PointerMember(base, _field) => {
+                self.lower_expr(base)?;
+                let var = match self.fresh() {
+                    Type::Var(id) => id,
+                    _ => unreachable!("fresh() always produces Type::Var"),
+                };
+                Err(TypeError::Unresolved(var))
+            }
+            Expression::Cast(spec, inner) => {
+                let hir = self.lower_expr(inner)?;
+                let ty = resolve_type_specifier(spec);
+                if hir.ty == ty {
+                    Ok(HirExpr { kind: hir.kind, ty })
+                } else {
+                    let coercion = cast_coercion(&hir.ty, &ty);
+                    Ok(HirExpr { kind: HirExprKind::Coerce(Box::new(hir), coercion), ty })
+                }
+            }
+        }
+    }
+
+    // C's usual arithmetic conversions, modeled as explicit coercion nodes
+    // rather than silent unification so the HIR stays faithful to the source.
+    fn usual_arithmetic_conversions(&mut self, lhs: HirExpr, rhs: HirExpr) -> Result<(HirExpr, HirExpr, Type), TypeError> {
+        if !lhs.ty.is_arithmetic() || !rhs.ty.is_arithmetic() {
+            let resolved = unify(&mut self.subst, &lhs.ty, &rhs.ty)?;
+            return Ok((lhs, rhs, resolved));
+        }
+        let common = rank_max(&lhs.ty, &rhs.ty);
+        let lhs = coerce(lhs, &common.clone(), &common);
+        let rhs = coerce(rhs, &common.clone(), &common);
+        Ok((lhs, rhs, common))
+    }
+}
+
+fn is_comparison_or_logical(op: &BinaryOp) -> bool {
+    use BinaryOp::*;
+    matches!(
+        op,
+        Equal | NotEqual | GreaterThan | GreaterThanEqual | LessThan | LessThanEqual | LogicalAnd | LogicalOr
+    )
+}
+
+fn rank(ty: &Type) -> u8 {
+    match ty {
+        Type::Char { .. } => 0,
+        Type::Int { .. } => 1,
+        Type::Long { .. } => 2,
+        Type::Double => 3,
+        _ => 1,
+    }
+}
+
+fn rank_max(a: &Type, b: &Type) -> Type {
+    if rank(a) >= rank(b) { a.clone() } else { b.clone() }
+}
+
+fn coerce(expr: HirExpr, target: &Type, resolved: &Type) -> HirExpr {
+    if &expr.ty == target {
+        return expr;
+    }
+    let coercion = match (&expr.ty, resolved) {
+        (from, Type::Double) if from.is_integer() => Coercion::ArithmeticConversion,
+        (from, to) if from.is_integer() && to.is_integer() && rank(from) < rank(to) => Coercion::IntegerPromotion,
+        (Type::Array(_, _), Type::Pointer(_)) => Coercion::ArrayDecay,
+        _ => Coercion::ArithmeticConversion,
+    };
+    HirExpr { kind: HirExprKind::Coerce(Box::new(expr), coercion), ty: resolved.clone() }
+}
+
+// Unlike `coerce()`, which only ever widens or promotes (C's implicit
+// conversions never narrow), an explicit cast can go in any direction, so
+// this picks the conversion from the real from/to types instead of
+// defaulting to a no-op pointer cast.
+fn cast_coercion(from: &Type, to: &Type) -> Coercion {
+    match (from, to) {
+        (from, Type::Double) if from.is_integer() => Coercion::ArithmeticConversion,
+        (Type::Double, to) if to.is_integer() => Coercion::ArithmeticConversion,
+        (from, to) if from.is_integer() && to.is_integer() && rank(from) < rank(to) => Coercion::IntegerPromotion,
+        (from, to) if from.is_integer() && to.is_integer() && rank(from) > rank(to) => Coercion::Truncate,
+        _ => Coercion::PointerCast,
+    }
+}
+
+fn decay(expr: HirExpr) -> HirExpr {
+    match &expr.ty {
+        Type::Array(inner, _) => {
+            let ty = Type::Pointer(inner.clone());
+            HirExpr { kind: HirExprKind::Coerce(Box::new(expr), Coercion::ArrayDecay), ty }
+        }
+        _ => expr,
+    }
+}
+
+fn literal_type(lit: &crate::tokens::Literal) -> Type {
+    use crate::tokens::Literal::*;
+    match lit {
+        Integer(_) => Type::Int { signed: true },
+        Float(_) => Type::Double,
+        Char(_) => Type::Char { signed: true },
+        Str(_) => Type::Pointer(Box::new(Type::Char { signed: true })),
+    }
+}
+
+fn resolve_specifier(spec: &DeclarationSpecifier) -> Type {
+    let signed = !spec.ty.iter().any(|t| matches!(t, TypeSpecifier::Unsigned));
+    for ty in &spec.ty {
+        match ty {
+            TypeSpecifier::Void => return Type::Void,
+            TypeSpecifier::Char => return Type::Char { signed },
+            TypeSpecifier::Int => return Type::Int { signed },
+            TypeSpecifier::Long => return Type::Long { signed },
+            TypeSpecifier::Double => return Type::Double,
+            TypeSpecifier::Struct(name) => return Type::Struct(*name),
+            TypeSpecifier::Signed | TypeSpecifier::Unsigned => continue,
+        }
+    }
+    Type::Int { signed }
+}
+
+fn resolve_type_specifier(spec: &TypeSpecifier) -> Type {
+    match spec {
+        TypeSpecifier::Void => Type::Void,
+        TypeSpecifier::Char => Type::Char { signed: true },
+        TypeSpecifier::Int => Type::Int { signed: true },
+        TypeSpecifier::Long => Type::Long { signed: true },
+        TypeSpecifier::Double => Type::Double,
+        TypeSpecifier::Struct(name) => Type::Struct(*name),
+        TypeSpecifier::Signed => Type::Int { signed: true },
+        TypeSpecifier::Unsigned => Type::Int { signed: false },
+    }
+}
+
+fn desugar_assign_op(op: &AssignOp) -> BinaryOp {
+    match op {
+        AssignOp::Assign => unreachable!("plain assignment has its own lowering arm"),
+        AssignOp::Plus => BinaryOp::Add,
+        AssignOp::Minus => BinaryOp::Subtract,
+        AssignOp::Multiply => BinaryOp::Multiply,
+        AssignOp::Divide => BinaryOp::Divide,
+        AssignOp::Modulo => BinaryOp::Modulo,
+        AssignOp::BitwiseAnd => BinaryOp::BitwiseAnd,
+        AssignOp::BitwiseOr => BinaryOp::BitwiseOr,
+        AssignOp::BitwiseXor => BinaryOp::BitwiseXor,
+        AssignOp::LeftShift => BinaryOp::LeftShift,
+        AssignOp::RightShift => BinaryOp::RightShift,
+    }
+}
+
+fn clone_binary_op(op: &BinaryOp) -> BinaryOp {
+    // BinaryOp doesn't derive Clone; mirror it manually for the HIR, which
+    // needs to keep the operator alongside freshly-built operand nodes.
+    use crate::ast::AssignOp;
+    match op {
+        BinaryOp::Add => BinaryOp::Add,
+        BinaryOp::Subtract => BinaryOp::Subtract,
+        BinaryOp::Multiply => BinaryOp::Multiply,
+        BinaryOp::Divide => BinaryOp::Divide,
+        BinaryOp::Modulo => BinaryOp::Modulo,
+        BinaryOp::Equal => BinaryOp::Equal,
+        BinaryOp::NotEqual => BinaryOp::NotEqual,
+        BinaryOp::GreaterThan => BinaryOp::GreaterThan,
+        BinaryOp::GreaterThanEqual => BinaryOp::GreaterThanEqual,
+        BinaryOp::LessThan => BinaryOp::LessThan,
+        BinaryOp::LessThanEqual => BinaryOp::LessThanEqual,
+        BinaryOp::LogicalAnd => BinaryOp::LogicalAnd,
+        BinaryOp::LogicalOr => BinaryOp::LogicalOr,
+        BinaryOp::BitwiseAnd => BinaryOp::BitwiseAnd,
+        BinaryOp::BitwiseOr => BinaryOp::BitwiseOr,
+        BinaryOp::BitwiseXor => BinaryOp::BitwiseXor,
+        BinaryOp::LeftShift => BinaryOp::LeftShift,
+        BinaryOp::RightShift => BinaryOp::RightShift,
+        BinaryOp::Assign(op) => BinaryOp::Assign(match op {
+            AssignOp::Assign => AssignOp::Assign,
+            AssignOp::Plus => AssignOp::Plus,
+            AssignOp::Minus => AssignOp::Minus,
+            AssignOp::Multiply => AssignOp::Multiply,
+            AssignOp::Divide => AssignOp::Divide,
+            AssignOp::Modulo => AssignOp::Modulo,
+            AssignOp::BitwiseAnd => AssignOp::BitwiseAnd,
+            AssignOp::BitwiseOr => AssignOp::BitwiseOr,
+            AssignOp::BitwiseXor => AssignOp::BitwiseXor,
+            AssignOp::LeftShift => AssignOp::LeftShift,
+            AssignOp::RightShift => AssignOp::RightShift,
+        }),
+    }
+}
+
+pub fn lower(ast: &ASTRoot) -> CompilerResult<Vec<HirDeclaration>, TypeError> {
+    let mut lowering = Lowering::new();
+    let mut decls = Vec::with_capacity(ast.len());
+    for item in ast {
+        match item {
+            InitDeclaration::Function(func) => {
+                let ty = lowering.resolve_declarator(&func.declaration.specifier, &func.declaration.declarator);
+                if let Some(name) = func.declaration.ident {
+                    let params = func
+                        .parameters
+                        .iter()
+                        .map(|p| lowering.resolve_declarator(&p.specifier, &p.declarator))
+                        .collect();
+                    lowering.bind(name, Type::Func { ret: Box::new(ty), params });
+                }
+            }
+            InitDeclaration::Declaration(var) => {
+                let ty = lowering.resolve_declarator(&var.declaration.specifier, &var.declaration.declarator);
+                if let Some(name) = var.declaration.ident {
+                    lowering.bind(name, ty);
+                }
+            }
+            InitDeclaration::Struct(_) => {}
+        }
+    }
+    for item in ast {
+        match item {
+            InitDeclaration::Function(func) => decls.push(HirDeclaration::Function(lowering.lower_function(func)?)),
+            InitDeclaration::Declaration(var) => {
+                let ty = lowering.resolve_declarator(&var.declaration.specifier, &var.declaration.declarator);
+                let ident = var.declaration.ident.expect("global declaration must be named");
+                let init = match &var.initializer {
+                    Some(expr) => {
+                        let hir = lowering.lower_expr(expr)?;
+                        let resolved = unify(&mut lowering.subst, &ty, &hir.ty)?;
+                        Some(coerce(hir, &ty, &resolved))
+                    }
+                    None => None,
+                };
+                decls.push(HirDeclaration::Global(ident, ty, init));
+            }
+            InitDeclaration::Struct(_) => {}
+        }
+    }
+    Ok(decls)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unify_binds_unresolved_var_to_concrete_type() {
+        let mut subst = Subst::default();
+        let result = unify(&mut subst, &Type::Var(0), &Type::Int { signed: true }).unwrap();
+        assert_eq!(result, Type::Int { signed: true });
+        assert_eq!(subst.resolve(&Type::Var(0)), Type::Int { signed: true });
+    }
+
+    #[test]
+    fn unify_rejects_mismatched_concrete_types() {
+        let mut subst = Subst::default();
+        let err = unify(&mut subst, &Type::Int { signed: true }, &Type::Double).unwrap_err();
+        assert!(matches!(err, TypeError::Mismatch { .. }));
+    }
+
+    #[test]
+    fn unify_detects_occurs_check_through_pointer() {
+        let mut subst = Subst::default();
+        let err = unify(&mut subst, &Type::Var(0), &Type::Pointer(Box::new(Type::Var(0)))).unwrap_err();
+        assert!(matches!(err, TypeError::OccursCheck { var: 0, .. }));
+    }
+
+    #[test]
+    fn desugar_assign_op_maps_compound_operators_to_arithmetic() {
+        assert_eq!(desugar_assign_op(&AssignOp::Plus), BinaryOp::Add);
+        assert_eq!(desugar_assign_op(&AssignOp::Minus), BinaryOp::Subtract);
+        assert_eq!(desugar_assign_op(&AssignOp::BitwiseXor), BinaryOp::BitwiseXor);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::ast::{BinaryOp, Expression, Fixity};
+use crate::tokens::Token;
+use crate::util::CompilerResult;
+
+#[derive(Debug)]
+pub enum PrecedenceError<E> {
+    Operand(E),
+}
+
+// Minimal interface the climber needs from whatever token source the parser
+// is built on, so this combinator doesn't have to know about the lexer,
+// the file/line bookkeeping, or how errors are reported.
+pub trait TokenCursor {
+    type Error;
+
+    fn peek(&self) -> Option<&Token>;
+    fn bump(&mut self) -> Option<Token>;
+    fn parse_unary(&mut self) -> Result<Expression, Self::Error>;
+}
+
+// Precedence-climbing (a restricted Pratt parser) for the operators in
+// `BinaryOp`. Parses one unary/primary operand, then repeatedly consumes an
+// operator whose precedence is at least `min_prec`, recursing for the right
+// operand with a raised minimum so lower-precedence operators stop the
+// recursion and return control to the caller.
+//
+// The minimum passed to the recursive call is what encodes associativity:
+// left-associative operators recurse with `prec + 1`, which rejects another
+// operator at the same precedence and lets the *caller's* loop pick it back
+// up on the left; right-associative operators (assignment) recurse with
+// `prec`, which keeps eating same-precedence operators into the right
+// operand instead, giving `a = b = c` its right-nested shape.
+pub fn parse_expression<C: TokenCursor>(
+    cursor: &mut C,
+    min_prec: u8,
+) -> CompilerResult<Expression, PrecedenceError<C::Error>> {
+    let mut lhs = cursor.parse_unary().map_err(PrecedenceError::Operand)?;
+
+    while let Some(op) = peek_binary_op(cursor) {
+        let prec = op.precedence();
+        if prec < min_prec {
+            break;
+        }
+        cursor.bump();
+
+        let next_min = match op.fixity() {
+            Fixity::Left => prec + 1,
+            Fixity::Right => prec,
+        };
+        let rhs = parse_expression(cursor, next_min)?;
+        lhs = build_binary(op, lhs, rhs);
+    }
+
+    Ok(lhs)
+}
+
+fn peek_binary_op<C: TokenCursor>(cursor: &C) -> Option<BinaryOp> {
+    cursor.peek().and_then(|tok| BinaryOp::try_from(tok).ok())
+}
+
+fn build_binary(op: BinaryOp, lhs: Expression, rhs: Expression) -> Expression {
+    match op {
+        BinaryOp::Assign(assign_op) => {
+            Expression::Binary(BinaryOp::Assign(assign_op), Box::new(lhs), Box::new(rhs))
+        }
+        other => Expression::Binary(other, Box::new(lhs), Box::new(rhs)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tokens::{Literal, Symbol};
+
+    // A trivial fixed-token-list cursor: `parse_unary` only ever has to
+    // handle a literal, which is all these associativity/precedence tests
+    // need from an operand.
+    struct ListCursor {
+        tokens: Vec<Token>,
+        pos: usize,
+    }
+
+    impl TokenCursor for ListCursor {
+        type Error = ();
+
+        fn peek(&self) -> Option<&Token> {
+            self.tokens.get(self.pos)
+        }
+
+        fn bump(&mut self) -> Option<Token> {
+            let tok = self.tokens.get(self.pos).cloned();
+            self.pos += 1;
+            tok
+        }
+
+        fn parse_unary(&mut self) -> Result<Expression, ()> {
+            match self.bump() {
+                Some(Token::Literal(lit)) => Ok(Expression::Literal(lit)),
+                _ => Err(()),
+            }
+        }
+    }
+
+    fn cursor(tokens: Vec<Token>) -> ListCursor {
+        ListCursor { tokens, pos: 0 }
+    }
+
+    fn int(n: i64) -> Token {
+        Token::Literal(Literal::Integer(n))
+    }
+
+    #[test]
+    fn left_associative_operators_nest_on_the_left() {
+        // "1 - 2 - 3"
+        let mut c = cursor(vec![int(1), Token::Symbol(Symbol::Minus), int(2), Token::Symbol(Symbol::Minus), int(3)]);
+        let expr = parse_expression(&mut c, 0).unwrap();
+        assert_eq!(expr.to_string(), "1 - 2 - 3");
+    }
+
+    #[test]
+    fn assignment_nests_on_the_right() {
+        // "1 = 2 = 3"
+        let mut c = cursor(vec![int(1), Token::Symbol(Symbol::Equal), int(2), Token::Symbol(Symbol::Equal), int(3)]);
+        let expr = parse_expression(&mut c, 0).unwrap();
+        assert_eq!(expr.to_string(), "1 = 2 = 3");
+    }
+
+    #[test]
+    fn higher_precedence_operator_binds_tighter() {
+        // "1 + 2 * 3" should parse as 1 + (2 * 3), not (1 + 2) * 3
+        let mut c = cursor(vec![int(1), Token::Symbol(Symbol::Plus), int(2), Token::Symbol(Symbol::Star), int(3)]);
+        let expr = parse_expression(&mut c, 0).unwrap();
+        assert_eq!(expr.to_string(), "1 + 2 * 3");
+    }
+}
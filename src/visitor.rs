@@ -0,0 +1,367 @@
+use crate::ast::{
+    Block, Declaration, Expression, FunctionDeclaration, InitDeclaration, Statement,
+    StructDeclaration, TypeOrExpression, VariableDeclaration,
+};
+
+// Immutable walk over the AST. Every method has a default that recurses into
+// the node's children via the matching `walk_*` free function, so a pass
+// only needs to override the variants it actually cares about and can still
+// call `walk_*` itself to keep recursing afterwards. `Declaration` is a leaf
+// here (no child has a `visit_*` method), so its default does nothing.
+pub trait Visitor: Sized {
+    fn visit_init_declaration(&mut self, item: &InitDeclaration) {
+        walk_init_declaration(self, item)
+    }
+
+    fn visit_function_declaration(&mut self, func: &FunctionDeclaration) {
+        walk_function_declaration(self, func)
+    }
+
+    fn visit_struct_declaration(&mut self, decl: &StructDeclaration) {
+        walk_struct_declaration(self, decl)
+    }
+
+    fn visit_declaration(&mut self, _decl: &Declaration) {}
+
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration) {
+        walk_variable_declaration(self, decl)
+    }
+
+    fn visit_block(&mut self, block: &Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement) {
+        walk_statement(self, stmt)
+    }
+
+    fn visit_expression(&mut self, expr: &Expression) {
+        walk_expression(self, expr)
+    }
+}
+
+pub fn walk_init_declaration<V: Visitor>(visitor: &mut V, item: &InitDeclaration) {
+    match item {
+        InitDeclaration::Declaration(decl) => visitor.visit_variable_declaration(decl),
+        InitDeclaration::Function(func) => visitor.visit_function_declaration(func),
+        InitDeclaration::Struct(decl) => visitor.visit_struct_declaration(decl),
+    }
+}
+
+pub fn walk_struct_declaration<V: Visitor>(visitor: &mut V, decl: &StructDeclaration) {
+    for member in &decl.members {
+        visitor.visit_declaration(member);
+    }
+}
+
+pub fn walk_function_declaration<V: Visitor>(visitor: &mut V, func: &FunctionDeclaration) {
+    visitor.visit_declaration(&func.declaration);
+    for param in &func.parameters {
+        visitor.visit_declaration(param);
+    }
+    if let Some(body) = &func.body {
+        visitor.visit_block(body);
+    }
+}
+
+pub fn walk_variable_declaration<V: Visitor>(visitor: &mut V, decl: &VariableDeclaration) {
+    visitor.visit_declaration(&decl.declaration);
+    if let Some(init) = &decl.initializer {
+        visitor.visit_expression(init);
+    }
+}
+
+pub fn walk_block<V: Visitor>(visitor: &mut V, block: &Block) {
+    for stmt in &block.0 {
+        visitor.visit_statement(stmt);
+    }
+}
+
+pub fn walk_statement<V: Visitor>(visitor: &mut V, stmt: &Statement) {
+    match stmt {
+        Statement::Expression(expr) => visitor.visit_expression(expr),
+        Statement::Declaration(decl) => visitor.visit_variable_declaration(decl),
+        Statement::If(cond, then, otherwise) => {
+            visitor.visit_expression(cond);
+            visitor.visit_statement(then);
+            if let Some(otherwise) = otherwise {
+                visitor.visit_statement(otherwise);
+            }
+        }
+        Statement::While(cond, body) => {
+            visitor.visit_expression(cond);
+            visitor.visit_statement(body);
+        }
+        Statement::For(init, cond, step, body) => {
+            if let Some(init) = init {
+                visitor.visit_variable_declaration(init);
+            }
+            if let Some(cond) = cond {
+                visitor.visit_expression(cond);
+            }
+            if let Some(step) = step {
+                visitor.visit_expression(step);
+            }
+            visitor.visit_statement(body);
+        }
+        Statement::Break | Statement::Continue => {}
+        Statement::Return(expr) => {
+            if let Some(expr) = expr {
+                visitor.visit_expression(expr);
+            }
+        }
+        Statement::Block(block) => visitor.visit_block(block),
+    }
+}
+
+pub fn walk_expression<V: Visitor>(visitor: &mut V, expr: &Expression) {
+    match expr {
+        Expression::Literal(_) | Expression::Variable(_) => {}
+        Expression::Sizeof(TypeOrExpression::Type(decl)) => visitor.visit_declaration(decl),
+        Expression::Sizeof(TypeOrExpression::Expr(inner)) => visitor.visit_expression(inner),
+        Expression::Parenthesized(inner)
+        | Expression::PostFix(_, inner)
+        | Expression::Unary(_, inner)
+        | Expression::Cast(_, inner) => visitor.visit_expression(inner),
+        Expression::Binary(_, lhs, rhs) => {
+            visitor.visit_expression(lhs);
+            visitor.visit_expression(rhs);
+        }
+        Expression::FunctionCall(_, args) => {
+            for arg in args {
+                visitor.visit_expression(arg);
+            }
+        }
+        Expression::Index(base, index) => {
+            visitor.visit_expression(base);
+            visitor.visit_expression(index);
+        }
+        Expression::Member(base, _) | Expression::PointerMember(base, _) => {
+            visitor.visit_expression(base)
+        }
+    }
+}
+
+// Owning rewrite over the AST: each method takes the node by value and
+// returns its (possibly replaced) rewrite, so a pass can e.g. swap a folded
+// `Binary` for a `Literal` instead of only observing it. Defaults delegate
+// to the matching `transform_*` free function, which rebuilds the node from
+// its *transformed* children so overrides still get to recurse first. The
+// leaf `Declaration` has no free function; its default returns it unchanged.
+pub trait Transformer: Sized {
+    fn transform_init_declaration(&mut self, item: InitDeclaration) -> InitDeclaration {
+        transform_init_declaration(self, item)
+    }
+
+    fn transform_function_declaration(&mut self, func: FunctionDeclaration) -> FunctionDeclaration {
+        transform_function_declaration(self, func)
+    }
+
+    fn transform_struct_declaration(&mut self, decl: StructDeclaration) -> StructDeclaration {
+        transform_struct_declaration(self, decl)
+    }
+
+    fn transform_declaration(&mut self, decl: Declaration) -> Declaration {
+        decl
+    }
+
+    fn transform_variable_declaration(&mut self, decl: VariableDeclaration) -> VariableDeclaration {
+        transform_variable_declaration(self, decl)
+    }
+
+    fn transform_block(&mut self, block: Block) -> Block {
+        transform_block(self, block)
+    }
+
+    fn transform_statement(&mut self, stmt: Statement) -> Statement {
+        transform_statement(self, stmt)
+    }
+
+    fn transform_expression(&mut self, expr: Expression) -> Expression {
+        transform_expression(self, expr)
+    }
+}
+
+pub fn transform_init_declaration<T: Transformer>(t: &mut T, item: InitDeclaration) -> InitDeclaration {
+    match item {
+        InitDeclaration::Declaration(decl) => {
+            InitDeclaration::Declaration(t.transform_variable_declaration(decl))
+        }
+        InitDeclaration::Function(func) => InitDeclaration::Function(t.transform_function_declaration(func)),
+        InitDeclaration::Struct(decl) => InitDeclaration::Struct(t.transform_struct_declaration(decl)),
+    }
+}
+
+pub fn transform_struct_declaration<T: Transformer>(t: &mut T, mut decl: StructDeclaration) -> StructDeclaration {
+    decl.members = decl.members.into_iter().map(|m| t.transform_declaration(m)).collect();
+    decl
+}
+
+pub fn transform_function_declaration<T: Transformer>(
+    t: &mut T,
+    mut func: FunctionDeclaration,
+) -> FunctionDeclaration {
+    func.declaration = t.transform_declaration(func.declaration);
+    func.parameters = func.parameters.into_iter().map(|p| t.transform_declaration(p)).collect();
+    func.body = func.body.map(|body| t.transform_block(body));
+    func
+}
+
+pub fn transform_variable_declaration<T: Transformer>(
+    t: &mut T,
+    decl: VariableDeclaration,
+) -> VariableDeclaration {
+    VariableDeclaration {
+        declaration: t.transform_declaration(decl.declaration),
+        initializer: decl.initializer.map(|e| t.transform_expression(e)),
+    }
+}
+
+pub fn transform_block<T: Transformer>(t: &mut T, block: Block) -> Block {
+    Block(block.0.into_iter().map(|s| t.transform_statement(s)).collect())
+}
+
+pub fn transform_statement<T: Transformer>(t: &mut T, stmt: Statement) -> Statement {
+    match stmt {
+        Statement::Expression(expr) => Statement::Expression(t.transform_expression(expr)),
+        Statement::Declaration(decl) => Statement::Declaration(t.transform_variable_declaration(decl)),
+        Statement::If(cond, then, otherwise) => Statement::If(
+            t.transform_expression(cond),
+            Box::new(t.transform_statement(*then)),
+            otherwise.map(|s| Box::new(t.transform_statement(*s))),
+        ),
+        Statement::While(cond, body) => {
+            Statement::While(t.transform_expression(cond), Box::new(t.transform_statement(*body)))
+        }
+        Statement::For(init, cond, step, body) => Statement::For(
+            init.map(|d| t.transform_variable_declaration(d)),
+            cond.map(|e| t.transform_expression(e)),
+            step.map(|e| t.transform_expression(e)),
+            Box::new(t.transform_statement(*body)),
+        ),
+        Statement::Break => Statement::Break,
+        Statement::Continue => Statement::Continue,
+        Statement::Return(expr) => Statement::Return(expr.map(|e| t.transform_expression(e))),
+        Statement::Block(block) => Statement::Block(t.transform_block(block)),
+    }
+}
+
+pub fn transform_expression<T: Transformer>(t: &mut T, expr: Expression) -> Expression {
+    match expr {
+        literal @ Expression::Literal(_) => literal,
+        variable @ Expression::Variable(_) => variable,
+        Expression::Sizeof(TypeOrExpression::Type(decl)) => {
+            Expression::Sizeof(TypeOrExpression::Type(t.transform_declaration(decl)))
+        }
+        Expression::Sizeof(TypeOrExpression::Expr(inner)) => {
+            Expression::Sizeof(TypeOrExpression::Expr(Box::new(t.transform_expression(*inner))))
+        }
+        Expression::Parenthesized(inner) => Expression::Parenthesized(Box::new(t.transform_expression(*inner))),
+        Expression::PostFix(op, inner) => Expression::PostFix(op, Box::new(t.transform_expression(*inner))),
+        Expression::Unary(op, inner) => Expression::Unary(op, Box::new(t.transform_expression(*inner))),
+        Expression::Cast(spec, inner) => Expression::Cast(spec, Box::new(t.transform_expression(*inner))),
+        Expression::Binary(op, lhs, rhs) => Expression::Binary(
+            op,
+            Box::new(t.transform_expression(*lhs)),
+            Box::new(t.transform_expression(*rhs)),
+        ),
+        Expression::FunctionCall(ident, args) => {
+            Expression::FunctionCall(ident, args.into_iter().map(|a| t.transform_expression(a)).collect())
+        }
+        Expression::Index(base, index) => Expression::Index(
+            Box::new(t.transform_expression(*base)),
+            Box::new(t.transform_expression(*index)),
+        ),
+        Expression::Member(base, field) => Expression::Member(Box::new(t.transform_expression(*base)), field),
+        Expression::PointerMember(base, field) => {
+            Expression::PointerMember(Box::new(t.transform_expression(*base)), field)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::BinaryOp;
+    use crate::tokens::Literal;
+
+    fn int(n: i64) -> Expression {
+        Expression::Literal(Literal::Integer(n))
+    }
+
+    // Counts integer literals reachable from whatever node it's pointed at,
+    // exercising every `walk_*` that has more than one child shape.
+    struct LiteralCounter {
+        count: usize,
+    }
+
+    impl Visitor for LiteralCounter {
+        fn visit_expression(&mut self, expr: &Expression) {
+            if let Expression::Literal(Literal::Integer(_)) = expr {
+                self.count += 1;
+            }
+            walk_expression(self, expr);
+        }
+    }
+
+    #[test]
+    fn visitor_reaches_literals_nested_under_control_flow_statements() {
+        let block = Block(vec![
+            Statement::If(
+                Expression::Binary(BinaryOp::LessThan, Box::new(int(1)), Box::new(int(2))),
+                Box::new(Statement::Return(Some(int(3)))),
+                Some(Box::new(Statement::Return(Some(int(4))))),
+            ),
+            Statement::While(int(5), Box::new(Statement::Expression(int(6)))),
+            Statement::For(
+                None,
+                Some(int(7)),
+                Some(int(8)),
+                Box::new(Statement::Block(Block(vec![Statement::Break]))),
+            ),
+        ]);
+
+        let mut counter = LiteralCounter { count: 0 };
+        counter.visit_block(&block);
+
+        assert_eq!(counter.count, 8);
+    }
+
+    // Replaces every integer literal with zero, proving the default
+    // `transform_*` methods rebuild nodes from *transformed* children rather
+    // than the originals.
+    struct Zeroer;
+
+    impl Transformer for Zeroer {
+        fn transform_expression(&mut self, expr: Expression) -> Expression {
+            match expr {
+                Expression::Literal(Literal::Integer(_)) => int(0),
+                other => transform_expression(self, other),
+            }
+        }
+    }
+
+    #[test]
+    fn transformer_rewrites_nested_literals_in_place() {
+        let expr = Expression::Binary(
+            BinaryOp::Add,
+            Box::new(int(1)),
+            Box::new(Expression::Parenthesized(Box::new(int(2)))),
+        );
+
+        let rewritten = Zeroer.transform_expression(expr);
+
+        match rewritten {
+            Expression::Binary(BinaryOp::Add, lhs, rhs) => {
+                assert!(matches!(*lhs, Expression::Literal(Literal::Integer(0))));
+                match *rhs {
+                    Expression::Parenthesized(inner) => {
+                        assert!(matches!(*inner, Expression::Literal(Literal::Integer(0))));
+                    }
+                    other => panic!("expected Parenthesized, got {other:?}"),
+                }
+            }
+            other => panic!("expected Binary, got {other:?}"),
+        }
+    }
+}